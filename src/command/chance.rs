@@ -0,0 +1,70 @@
+use lang::Check;
+use locale::Locale;
+
+use crate::command::*;
+
+/// Rolls simulated when the exact chance is too expensive.
+const SAMPLES: usize = 10_000;
+
+/// Show the chance of a roll reaching a target.
+///
+/// With a single d20, also show how natural 20s and 1s change it.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    name_localized("fr", "chances"),
+    description_localized("fr", "Afficher les chances d'un lancer d'atteindre une cible.")
+)]
+pub async fn chance(
+    ctx: Context<'_>,
+    #[description = "Roll and target, like `2d20:adv + 7 >= 15`"]
+    #[name_localized("fr", "test")]
+    #[description_localized("fr", "Lancer et cible, par exemple `2d20:adv + 7 >= 15`")]
+    check: Check,
+) -> Result {
+    let limits = &ctx.data().limits;
+    check.tokens().check_limits(limits)?;
+
+    let catalog = Locale::of(ctx).catalog();
+    let formula = check.to_string();
+    // The exact chances can take a few seconds too
+    let (check, chances) = tokio::task::spawn_blocking(move || {
+        let chances = check.chance().map(|chance| (chance, check.naturals()));
+        (check, chances)
+    })
+    .await?;
+    let reply = match chances {
+        Some((chance, naturals)) => {
+            let chance = catalog.chance(&formula, chance * 100.0);
+            match naturals {
+                Some(naturals) => format!(
+                    "{chance}, {}",
+                    catalog.chance_naturals(
+                        naturals.twenty * 100.0,
+                        naturals.one * 100.0,
+                        naturals.chance * 100.0
+                    )
+                ),
+                None => chance,
+            }
+        }
+        None => {
+            let seed = rand::random();
+            let estimate = tokio::task::spawn_blocking(move || {
+                check.estimate(&check.tokens().simulate(SAMPLES, seed))
+            })
+            .await?;
+            format!(
+                "{}\n{}",
+                catalog.chance_estimated(
+                    &formula,
+                    estimate.value * 100.0,
+                    estimate.low * 100.0,
+                    estimate.high * 100.0
+                ),
+                catalog.odds_simulated(SAMPLES, seed)
+            )
+        }
+    };
+    say(ctx, reply).await
+}
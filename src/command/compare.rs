@@ -0,0 +1,144 @@
+use lang::{Distribution, Versus};
+use locale::{Catalog, Locale};
+use table::{Alignment, Table, TableRow};
+
+use crate::command::*;
+
+const HISTOGRAM_ROWS: usize = 20;
+/// Rolls simulated for each side when an exact distribution is too expensive.
+const SAMPLES: usize = 10_000;
+
+fn row(label: String, left: String, right: String) -> TableRow {
+    TableRow::columns(vec![
+        (label, Alignment::Left),
+        (left, Alignment::Right),
+        (right, Alignment::Right),
+    ])
+}
+
+fn percent(p: f64) -> String {
+    format!("{:.1}%", p * 100.0)
+}
+
+/// Both distributions side by side, with the chances of each side to roll higher.
+fn table(
+    versus: &Versus,
+    left: &Distribution,
+    right: &Distribution,
+    catalog: &dyn Catalog,
+) -> Table {
+    let mut table = Table::new(vec![
+        TableRow::FullWidth(format!("A: {}", versus.left()), Alignment::Left),
+        TableRow::FullWidth(format!("B: {}", versus.right()), Alignment::Left),
+        TableRow::Separator('#'),
+        row(String::new(), "A".to_string(), "B".to_string()),
+        row(
+            catalog.stat_min(),
+            left.min().to_string(),
+            right.min().to_string(),
+        ),
+        row(
+            catalog.stat_max(),
+            left.max().to_string(),
+            right.max().to_string(),
+        ),
+        row(
+            catalog.stat_mean(),
+            format!("{:.2}", left.mean()),
+            format!("{:.2}", right.mean()),
+        ),
+        row(
+            catalog.stat_std_dev(),
+            format!("{:.2}", left.std_dev()),
+            format!("{:.2}", right.std_dev()),
+        ),
+        TableRow::Separator('-'),
+        row(catalog.compare_total(), "A".to_string(), "B".to_string()),
+    ]);
+
+    let (min, max) = (left.min().min(right.min()), left.max().max(right.max()));
+    table.append_rows(
+        &left
+            .buckets_between(min, max, HISTOGRAM_ROWS)
+            .into_iter()
+            .zip(right.buckets_between(min, max, HISTOGRAM_ROWS))
+            .map(|((label, left), (_, right))| row(label, percent(left), percent(right)))
+            .collect::<Vec<_>>(),
+    );
+
+    let (wins, losses) = (left.beats(right), right.beats(left));
+    let difference = left.mean() - right.mean();
+    table.append_rows(&[
+        TableRow::Separator('='),
+        row(catalog.compare_wins(), percent(wins), percent(losses)),
+        TableRow::columns(vec![
+            (catalog.compare_ties(), Alignment::Left),
+            (percent(1.0 - wins - losses), Alignment::Right),
+        ]),
+        row(
+            catalog.compare_mean_difference(),
+            format!("{difference:+.2}"),
+            format!("{:+.2}", -difference),
+        ),
+    ]);
+
+    table
+}
+
+/// Compare the probabilities of two rolls.
+///
+/// They are exact when possible, otherwise estimated by simulating many rolls of each side.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    name_localized("fr", "comparer"),
+    description_localized("fr", "Comparer les probabilités de deux lancers.")
+)]
+pub async fn compare(
+    ctx: Context<'_>,
+    #[description = "Rolls to compare, like `2d6 + 3 vs 1d12 + 4`"]
+    #[name_localized("fr", "lancers")]
+    #[description_localized("fr", "Lancers à comparer, par exemple `2d6 + 3 vs 1d12 + 4`")]
+    versus: Versus,
+) -> Result {
+    let limits = &ctx.data().limits;
+    versus.check_limits(limits)?;
+
+    let catalog = Locale::of(ctx).catalog();
+    let style = ctx.data().guild_settings(ctx.guild_id()).style;
+    // The exact distributions can take a few seconds too
+    let (versus, distributions) = tokio::task::spawn_blocking(move || {
+        let distributions = (versus.left().distribution(), versus.right().distribution());
+        (versus, distributions)
+    })
+    .await?;
+    let reply = match distributions {
+        (Some(left), Some(right)) => format!(
+            "\
+```rust
+{}
+```
+",
+            table(&versus, &left, &right, catalog).render(style)
+        ),
+        _ => {
+            let seed = rand::random();
+            let (versus, left, right) = tokio::task::spawn_blocking(move || {
+                let left = versus.left().simulate(SAMPLES, seed).distribution();
+                let right = versus.right().simulate(SAMPLES, seed).distribution();
+                (versus, left, right)
+            })
+            .await?;
+            format!(
+                "\
+```rust
+{}
+```
+{}",
+                table(&versus, &left, &right, catalog).render(style),
+                catalog.compare_simulated(SAMPLES, seed)
+            )
+        }
+    };
+    say(ctx, reply).await
+}
@@ -0,0 +1,165 @@
+use locale::{Locale, Localize};
+use poise::ChoiceParameter;
+use render::Output;
+use table::Style;
+
+use crate::command::*;
+
+/// Show the settings of this server.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    rename = "settings",
+    subcommands("style", "output", "pool", "language"),
+    name_localized("fr", "parametres"),
+    description_localized("fr", "Afficher les paramètres de ce serveur.")
+)]
+pub async fn configure(ctx: Context<'_>) -> Result {
+    let locale = Locale::of(ctx);
+    let catalog = locale.catalog();
+    let settings = ctx.data().guild_settings(ctx.guild_id());
+
+    ctx.say(format!(
+        "{}\n{}\n{}\n{}",
+        catalog.settings_style(&settings.style.localize(locale)),
+        catalog.settings_output(&settings.output.localize(locale)),
+        catalog.settings_pool_threshold(settings.pool_threshold),
+        catalog.settings_locale(settings.locale.map(|locale| locale.name()))
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Set how tables are drawn in this server.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    name_localized("fr", "style"),
+    description_localized("fr", "Choisir comment les tableaux sont dessinés sur ce serveur.")
+)]
+pub async fn style(
+    ctx: Context<'_>,
+    #[description = "Style of the tables"]
+    #[name_localized("fr", "style")]
+    #[description_localized("fr", "Style des tableaux")]
+    style: Style,
+) -> Result {
+    let locale = Locale::of(ctx);
+    let guild = ctx.guild_id().expect("The command is guild only");
+    ctx.data()
+        .settings
+        .lock()
+        .expect("Settings are not poisoned")
+        .update(guild, |settings| settings.style = style)?;
+
+    ctx.say(locale.catalog().settings_style(&style.localize(locale)))
+        .await?;
+
+    Ok(())
+}
+
+/// Set how rolls are posted in this server.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    name_localized("fr", "affichage"),
+    description_localized("fr", "Choisir comment les lancers sont postés sur ce serveur.")
+)]
+pub async fn output(
+    ctx: Context<'_>,
+    #[description = "How rolls are posted"]
+    #[name_localized("fr", "affichage")]
+    #[description_localized("fr", "Comment les lancers sont postés")]
+    output: Output,
+) -> Result {
+    let locale = Locale::of(ctx);
+    let guild = ctx.guild_id().expect("The command is guild only");
+    ctx.data()
+        .settings
+        .lock()
+        .expect("Settings are not poisoned")
+        .update(guild, |settings| settings.output = output)?;
+
+    ctx.say(locale.catalog().settings_output(&output.localize(locale)))
+        .await?;
+
+    Ok(())
+}
+
+/// Set how many dice a roll can have before they are shown as face counts in this server.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    name_localized("fr", "reserve"),
+    description_localized(
+        "fr",
+        "Choisir à partir de combien de dés un lancer est résumé par face sur ce serveur."
+    )
+)]
+pub async fn pool(
+    ctx: Context<'_>,
+    #[description = "Most dice shown one per row"]
+    #[name_localized("fr", "seuil")]
+    #[description_localized("fr", "Nombre de dés au-delà duquel les faces sont comptées")]
+    #[min = 1]
+    threshold: u16,
+) -> Result {
+    let locale = Locale::of(ctx);
+    let guild = ctx.guild_id().expect("The command is guild only");
+    ctx.data()
+        .settings
+        .lock()
+        .expect("Settings are not poisoned")
+        .update(guild, |settings| settings.pool_threshold = threshold)?;
+
+    ctx.say(locale.catalog().settings_pool_threshold(threshold))
+        .await?;
+
+    Ok(())
+}
+
+/// Set the language of the replies in this server, or follow the one of each member.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    guild_only,
+    required_permissions = "MANAGE_GUILD",
+    name_localized("fr", "langue"),
+    description_localized(
+        "fr",
+        "Choisir la langue des réponses sur ce serveur, ou suivre celle de chaque membre."
+    )
+)]
+pub async fn language(
+    ctx: Context<'_>,
+    #[description = "Language of the replies, the one of each member when left out"]
+    #[name_localized("fr", "langue")]
+    #[description_localized("fr", "Langue des réponses, celle de chaque membre si omise")]
+    language: Option<Locale>,
+) -> Result {
+    let guild = ctx.guild_id().expect("The command is guild only");
+    ctx.data()
+        .settings
+        .lock()
+        .expect("Settings are not poisoned")
+        .update(guild, |settings| settings.locale = language)?;
+
+    // Reply in the language that was just chosen
+    let locale = Locale::of(ctx);
+    ctx.say(
+        locale
+            .catalog()
+            .settings_locale(language.map(|language| language.name())),
+    )
+    .await?;
+
+    Ok(())
+}
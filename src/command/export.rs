@@ -0,0 +1,49 @@
+use locale::Locale;
+use poise::{serenity_prelude::CreateAttachment, CreateReply};
+use table::Format;
+
+use crate::command::*;
+
+/// Download the table of a past roll, to paste it in a wiki or a spreadsheet.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    name_localized("fr", "exporter"),
+    description_localized(
+        "fr",
+        "Télécharger le tableau d'un lancer passé, pour le coller dans un wiki ou un tableur."
+    )
+)]
+pub async fn export(
+    ctx: Context<'_>,
+    #[description = "ID of the roll, shown under it"]
+    #[name_localized("fr", "id")]
+    #[description_localized("fr", "Identifiant du lancer, affiché en dessous")]
+    id: String,
+    #[description = "Format of the file"]
+    #[description_localized("fr", "Format du fichier")]
+    format: Format,
+) -> Result {
+    let catalog = Locale::of(ctx).catalog();
+
+    let logged = ctx
+        .data()
+        .log
+        .lock()
+        .expect("Log is not poisoned")
+        .get(&id)
+        .cloned();
+    let reply = match logged {
+        Some(logged) => CreateReply::default().attachment(CreateAttachment::bytes(
+            render::table(&logged.roll)
+                .await?
+                .export(format)
+                .into_bytes(),
+            format!("roll-{}.{}", logged.id, format.extension()),
+        )),
+        None => CreateReply::default().content(catalog.replay_not_found(&id)),
+    };
+    ctx.send(reply).await?;
+
+    Ok(())
+}
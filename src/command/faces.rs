@@ -0,0 +1,95 @@
+use std::{collections::HashMap, num::NonZeroU8};
+
+use lang::RolledTokens;
+
+use crate::command::*;
+
+/// How many times every face of every kind of die was rolled.
+pub struct Faces {
+    counts: HashMap<NonZeroU8, Vec<u64>>,
+}
+
+impl Faces {
+    pub fn new() -> Self {
+        Self {
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Counts of every roll in `log`, so they outlive restarts.
+    pub fn from_log(log: &RollLog) -> Self {
+        let mut faces = Self::new();
+        for logged in log.rolls() {
+            faces.record(&logged.roll.rolled);
+        }
+        faces
+    }
+
+    /// Count every die thrown by a roll, kept or not.
+    pub fn record(&mut self, rolled: &RolledTokens) {
+        for die in rolled.dice() {
+            let counts = self
+                .counts
+                .entry(die.sides())
+                .or_insert_with(|| vec![0; u8::from(die.sides()) as usize]);
+            counts[die.value() as usize - 1] += 1;
+        }
+    }
+
+    /// Count of every face of dice with `sides`, from 1 up.
+    pub fn counts(&self, sides: NonZeroU8) -> Vec<u64> {
+        self.counts
+            .get(&sides)
+            .cloned()
+            .unwrap_or_else(|| vec![0; u8::from(sides) as usize])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use super::*;
+    use lang::{SeededRng, Tokens};
+    use roll_log::Roll;
+
+    #[test]
+    fn record_counts_every_die() {
+        let mut faces = Faces::new();
+        let tokens = "4d6:adv3 + d20 + 2d6 - 1".parse::<Tokens>().unwrap();
+        let mut rng = SeededRng::seed_from_u64(3);
+        for _ in 0..10 {
+            faces.record(&tokens.roll(&mut rng));
+        }
+
+        let d6 = faces.counts(NonZeroU8::new(6).unwrap());
+        assert_eq!(d6.len(), 6);
+        assert_eq!(d6.iter().sum::<u64>(), 60);
+        assert_eq!(
+            faces
+                .counts(NonZeroU8::new(20).unwrap())
+                .iter()
+                .sum::<u64>(),
+            10
+        );
+        assert_eq!(faces.counts(NonZeroU8::new(8).unwrap()), vec![0; 8]);
+    }
+
+    #[test]
+    fn from_log_counts_logged_rolls() {
+        let mut log = RollLog::in_memory();
+        for _ in 0..4 {
+            log.record(Roll::test("3d6 + d20"));
+        }
+
+        let faces = Faces::from_log(&log);
+        let sum = |sides| {
+            faces
+                .counts(NonZeroU8::new(sides).unwrap())
+                .iter()
+                .sum::<u64>()
+        };
+        assert_eq!(sum(6), 12);
+        assert_eq!(sum(20), 4);
+    }
+}
@@ -0,0 +1,113 @@
+use lang::{ChiSquared, Tokens, MIN_EXPECTED};
+use locale::Locale;
+use table::{Alignment, Table, TableRow};
+
+use crate::command::*;
+
+/// Below this p-value, the faces are deemed too uneven for a fair die.
+const SIGNIFICANCE: f64 = 0.01;
+
+/// Test whether the faces rolled so far are consistent with a fair die.
+///
+/// Runs a chi-squared goodness-of-fit test on every die of that kind in the roll log, kept or
+/// not.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    name_localized("fr", "equite"),
+    description_localized(
+        "fr",
+        "Tester si les faces obtenues jusqu'ici sont celles d'un dé équilibré."
+    )
+)]
+pub async fn fairness(
+    ctx: Context<'_>,
+    #[description = "Kind of die to test, like `d20`"]
+    #[name_localized("fr", "de")]
+    #[description_localized("fr", "Type de dé à tester, par exemple `d20`")]
+    die: Tokens,
+) -> Result {
+    let catalog = Locale::of(ctx).catalog();
+    let Some(sides) = die.die_sides() else {
+        ctx.say(catalog.fairness_not_die()).await?;
+        return Ok(());
+    };
+
+    let counts = ctx
+        .data()
+        .faces
+        .lock()
+        .expect("Faces are not poisoned")
+        .counts(sides);
+    let Some(test) = ChiSquared::uniform(&counts) else {
+        ctx.say(catalog.fairness_no_rolls(u8::from(sides))).await?;
+        return Ok(());
+    };
+
+    let total = counts.iter().sum::<u64>();
+    let expected = 1.0 / counts.len() as f64;
+    let mut table = Table::new(vec![
+        TableRow::FullWidth(
+            catalog.fairness_header(u8::from(sides), total),
+            Alignment::Left,
+        ),
+        TableRow::Separator('#'),
+        TableRow::columns(vec![
+            (catalog.fairness_face(), Alignment::Right),
+            (catalog.fairness_count(), Alignment::Right),
+            (catalog.fairness_share(), Alignment::Right),
+            (catalog.fairness_deviation(), Alignment::Right),
+        ]),
+        TableRow::Separator('-'),
+    ]);
+    table.append_rows(
+        &counts
+            .iter()
+            .enumerate()
+            .map(|(face, count)| {
+                let share = *count as f64 / total as f64;
+                TableRow::columns(vec![
+                    ((face + 1).to_string(), Alignment::Right),
+                    (count.to_string(), Alignment::Right),
+                    (format!("{:.1}%", share * 100.0), Alignment::Right),
+                    (
+                        format!("{:+.1}%", (share - expected) * 100.0),
+                        Alignment::Right,
+                    ),
+                ])
+            })
+            .collect::<Vec<_>>(),
+    );
+    table.append_rows(&[
+        TableRow::Separator('='),
+        TableRow::columns(vec![
+            (catalog.fairness_statistic(), Alignment::Right),
+            (format!("{:.2}", test.statistic), Alignment::Right),
+        ]),
+        TableRow::columns(vec![
+            (catalog.fairness_degrees_of_freedom(), Alignment::Right),
+            (test.degrees_of_freedom.to_string(), Alignment::Right),
+        ]),
+        TableRow::columns(vec![
+            (catalog.fairness_p_value(), Alignment::Right),
+            (format!("{:.4}", test.p_value), Alignment::Right),
+        ]),
+    ]);
+
+    let verdict = if (total as f64) * expected < MIN_EXPECTED {
+        catalog.fairness_too_few((MIN_EXPECTED / expected).ceil() as u64)
+    } else if test.p_value < SIGNIFICANCE {
+        catalog.fairness_unfair(test.p_value)
+    } else {
+        catalog.fairness_fair(test.p_value)
+    };
+    let table = table.render(ctx.data().guild_settings(ctx.guild_id()).style);
+    let reply = format!(
+        "\
+```rust
+{table}
+```
+{verdict}"
+    );
+    say(ctx, reply).await
+}
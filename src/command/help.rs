@@ -1,11 +1,39 @@
+use locale::Locale;
+
 use crate::command::*;
 
 /// Show all available commands and how to use them.
-#[poise::command(prefix_command, track_edits)]
+#[poise::command(
+    slash_command,
+    prefix_command,
+    track_edits,
+    name_localized("fr", "aide"),
+    description_localized("fr", "Afficher toutes les commandes disponibles et leur utilisation.")
+)]
 pub async fn help(
     ctx: Context<'_>,
-    #[description = "Specific command to show help about"] command: Option<String>,
+    #[description = "Specific command to show help about"]
+    #[name_localized("fr", "commande")]
+    #[description_localized("fr", "Commande précise sur laquelle afficher l'aide")]
+    command: Option<String>,
 ) -> Result {
+    let locale = Locale::of(ctx);
+
+    // Localized names are accepted too, e.g. `/aide lancer`
+    let command = command.map(|command| {
+        ctx.framework()
+            .options()
+            .commands
+            .iter()
+            .find(|c| c.name_localizations.values().any(|name| *name == command))
+            .map_or(command, |c| c.name.clone())
+    });
+
+    if locale != Locale::English && command.as_deref() == Some("roll") {
+        ctx.say(locale.catalog().roll_help()).await?;
+        return Ok(());
+    }
+
     let config = poise::builtins::HelpConfiguration::default();
     poise::builtins::help(ctx, command.as_deref(), config).await?;
 
@@ -0,0 +1,400 @@
+use std::{
+    fmt::Display,
+    num::{NonZeroU8, ParseIntError},
+    str::FromStr,
+};
+
+use regex::Regex;
+
+use super::{
+    diagnostic::{Diagnostic, ErrorCode},
+    distribution::Distribution,
+    error::ParseError,
+    error_token::ParseTokenError,
+    simulation::{Estimate, Simulation},
+    Dice, Modifier, Token, Tokens,
+};
+use crate::command::locale::{Locale, Localize};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Comparison {
+    AtLeast,
+    MoreThan,
+    AtMost,
+    LessThan,
+}
+
+impl Comparison {
+    pub fn holds(&self, total: i32, target: i32) -> bool {
+        match self {
+            Comparison::AtLeast => total >= target,
+            Comparison::MoreThan => total > target,
+            Comparison::AtMost => total <= target,
+            Comparison::LessThan => total < target,
+        }
+    }
+}
+
+impl Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Comparison::AtLeast => ">=",
+                Comparison::MoreThan => ">",
+                Comparison::AtMost => "<=",
+                Comparison::LessThan => "<",
+            }
+        )
+    }
+}
+
+/// A roll against a target number, like `2d20:adv + 7 >= 15`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Check {
+    tokens: Tokens,
+    comparison: Comparison,
+    target: i32,
+}
+
+/// Chances of the natural 20 and 1 of the only d20 of a check.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Naturals {
+    pub twenty: f64,
+    pub one: f64,
+    /// Chance of the check when a natural 20 always succeeds and a natural 1 always fails.
+    pub chance: f64,
+}
+
+impl Check {
+    pub fn tokens(&self) -> &Tokens {
+        &self.tokens
+    }
+
+    /// Exact chance of the check succeeding, `None` if it is too expensive to compute.
+    pub fn chance(&self) -> Option<f64> {
+        self.tokens
+            .distribution()
+            .map(|distribution| self.chance_of(&distribution))
+    }
+
+    fn chance_of(&self, distribution: &Distribution) -> f64 {
+        distribution
+            .iter()
+            .filter(|(total, _)| self.comparison.holds(*total, self.target))
+            .map(|(_, p)| p)
+            .sum()
+    }
+
+    /// Chance of the check succeeding in the simulated rolls.
+    pub fn estimate(&self, simulation: &Simulation) -> Estimate<f64> {
+        simulation.share(|total| self.comparison.holds(total, self.target))
+    }
+
+    /// Breakdown of natural 20s and 1s when the check rolls exactly one d20 and keeps one die,
+    /// like `d20 + 5` or `2d20:adv + 5`, and wants to roll high.
+    pub fn naturals(&self) -> Option<Naturals> {
+        if !matches!(self.comparison, Comparison::AtLeast | Comparison::MoreThan) {
+            return None;
+        }
+
+        let is_d20 =
+            |token: &Token| matches!(token, Token::Die(dice, _) if u8::from(dice.sides()) == 20);
+        let mut d20s = self.tokens.0.iter().filter(|token| is_d20(token));
+        let d20 = match (d20s.next(), d20s.next()) {
+            (Some(d20), None) => d20,
+            _ => return None,
+        };
+        let one = NonZeroU8::new(1).expect("1 is not 0");
+        let keeps_one = match d20 {
+            Token::Die(dice, None) => dice.count() == one,
+            Token::Die(_, Some(Modifier::Advantage(take) | Modifier::Disadvantage(take))) => {
+                *take == one
+            }
+            Token::Constant(_) => false,
+        };
+        if !keeps_one {
+            return None;
+        }
+
+        let rest = Tokens(
+            self.tokens
+                .0
+                .iter()
+                .filter(|token| !is_d20(token))
+                .cloned()
+                .collect(),
+        )
+        .distribution()?;
+        let d20 = d20.distribution();
+        let chance = d20
+            .iter()
+            .map(|(face, p)| match face {
+                20 => p,
+                1 => 0.0,
+                face => {
+                    p * rest
+                        .iter()
+                        .filter(|(total, _)| self.comparison.holds(face + total, self.target))
+                        .map(|(_, p)| p)
+                        .sum::<f64>()
+                }
+            })
+            .sum();
+
+        Some(Naturals {
+            twenty: d20.probability(20),
+            one: d20.probability(1),
+            chance,
+        })
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.tokens, self.comparison, self.target)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CheckErrorKind {
+    Comparison,
+    Target(ParseIntError),
+    Tokens(ParseTokenError),
+}
+
+impl Localize for CheckErrorKind {
+    fn localize(&self, locale: Locale) -> String {
+        let catalog = locale.catalog();
+        match self {
+            CheckErrorKind::Comparison => catalog.check_comparison(),
+            CheckErrorKind::Target(e) => catalog.check_target(&e.localize(locale)),
+            CheckErrorKind::Tokens(e) => catalog.check_tokens(&e.localize(locale)),
+        }
+    }
+}
+
+impl Display for CheckErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.localize(Locale::English))
+    }
+}
+
+impl Diagnostic for CheckErrorKind {
+    fn code(&self) -> ErrorCode {
+        match self {
+            CheckErrorKind::Comparison => ErrorCode::new(500, "check-comparison-missing"),
+            CheckErrorKind::Target(_) => ErrorCode::new(501, "check-target-invalid"),
+            CheckErrorKind::Tokens(_) => ErrorCode::new(502, "check-tokens-invalid"),
+        }
+    }
+
+    fn cause(&self) -> Option<&dyn Diagnostic> {
+        match self {
+            CheckErrorKind::Comparison => None,
+            CheckErrorKind::Target(e) => Some(e),
+            CheckErrorKind::Tokens(e) => Some(e),
+        }
+    }
+}
+
+pub type ParseCheckError = ParseError<CheckErrorKind>;
+
+/// Tokens of a check, where a trailing `:adv` or `:dis` after the whole expression of a single
+/// die, like `d20 + 7:adv`, rolls that die twice and keeps the best or the worst, like
+/// `2d20:adv1 + 7`.
+///
+/// A modifier written on the die itself, like `d20:adv + 7`, keeps the meaning it has in `/roll`.
+fn parse_tokens(s: &str) -> Result<Tokens, ParseTokenError> {
+    let suffix = Regex::new(r"(?i)^(.*?)\s*[:\x{ff1a}]\s*(adv|dis)\s*$")
+        .unwrap()
+        .captures(s);
+    if let Some(captures) = suffix {
+        let tokens = captures
+            .get(1)
+            .expect("This group is always present in the capture")
+            .as_str()
+            .parse::<Tokens>();
+        let one = NonZeroU8::MIN;
+        let modifier = match captures
+            .get(2)
+            .expect("This group is always present in the capture")
+            .as_str()
+            .to_lowercase()
+            .as_str()
+        {
+            "adv" => Modifier::Advantage(one),
+            _ => Modifier::Disadvantage(one),
+        };
+        if let Ok(Tokens(mut tokens)) = tokens {
+            // After a die, like `d20:adv`, the modifier belongs to that die
+            let after_expression = matches!(tokens.last(), Some(Token::Constant(_)));
+            let mut dice = tokens
+                .iter_mut()
+                .filter(|token| matches!(token, Token::Die(..)));
+            if let (true, Some(Token::Die(dice, taken @ None)), None) =
+                (after_expression, dice.next(), dice.next())
+            {
+                if dice.count() == one {
+                    *dice = Dice::new(one.saturating_add(1), dice.sides());
+                    *taken = Some(modifier);
+                    return Ok(Tokens(tokens));
+                }
+            }
+        }
+    }
+    s.parse()
+}
+
+impl FromStr for Check {
+    type Err = ParseCheckError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.replace('\u{2265}', ">=").replace('\u{2264}', "<=");
+        let Some(captures) = Regex::new(r"^(.*?)(>=|<=|>|<)(.*)$")
+            .unwrap()
+            .captures(&normalized)
+        else {
+            return Err(Self::Err::new(s.to_string(), CheckErrorKind::Comparison));
+        };
+
+        let tokens = captures
+            .get(1)
+            .expect("This group is always present in the capture")
+            .as_str();
+        let tokens = parse_tokens(tokens)
+            .map_err(|e| Self::Err::new(s.to_string(), CheckErrorKind::Tokens(e)))?;
+
+        let comparison = match captures
+            .get(2)
+            .expect("This group is always present in the capture")
+            .as_str()
+        {
+            ">=" => Comparison::AtLeast,
+            ">" => Comparison::MoreThan,
+            "<=" => Comparison::AtMost,
+            _ => Comparison::LessThan,
+        };
+
+        let target = captures
+            .get(3)
+            .expect("This group is always present in the capture")
+            .as_str()
+            .trim();
+        let target = target
+            .parse::<i32>()
+            .map_err(|e| Self::Err::new(s.to_string(), CheckErrorKind::Target(e)))?;
+
+        Ok(Self {
+            tokens,
+            comparison,
+            target,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::command::lang::assert_close;
+
+    fn check(s: &str) -> Check {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_check_works() {
+        assert_eq!(
+            check("2d20:adv + 7 >= 15"),
+            Check {
+                tokens: "2d20:adv + 7".parse().unwrap(),
+                comparison: Comparison::AtLeast,
+                target: 15,
+            }
+        );
+        assert_eq!(check("d20+5>12").comparison, Comparison::MoreThan);
+        assert_eq!(check("d20 \u{2264} 3").comparison, Comparison::AtMost);
+        assert_eq!(check("d6 < -1").target, -1);
+        assert_eq!(check("D20 +7>=15").to_string(), "1d20 + 7 >= 15");
+    }
+
+    #[test]
+    fn parse_check_single_die_modifier_works() {
+        assert_eq!(check("d20+7:adv >= 15"), check("2d20:adv1 + 7 >= 15"));
+        assert_eq!(
+            check("5 + 1d20 - 1 : DIS < 10"),
+            check("5 + 2d20:dis1 - 1 < 10")
+        );
+        // Like in `/roll`, a modifier on the die keeps only that one die
+        assert_eq!(check("d20:adv >= 15"), check("1d20:adv1 >= 15"));
+        assert_eq!(check("5 + d20:dis < 10"), check("5 + 1d20:dis1 < 10"));
+        assert_eq!(
+            "2d20 + 7:adv >= 15".parse::<Check>().unwrap_err().code(),
+            ErrorCode::new(502, "check-tokens-invalid")
+        );
+        assert_eq!(
+            "d20 + d4 + 7:adv >= 15"
+                .parse::<Check>()
+                .unwrap_err()
+                .code(),
+            ErrorCode::new(502, "check-tokens-invalid")
+        );
+    }
+
+    #[test]
+    fn parse_check_fails() {
+        assert_eq!(
+            "d20 + 5".parse::<Check>(),
+            Err(ParseCheckError::new(
+                "d20 + 5".to_string(),
+                CheckErrorKind::Comparison
+            ))
+        );
+        assert_eq!(
+            "d20 >= x".parse::<Check>().unwrap_err().code(),
+            ErrorCode::new(501, "check-target-invalid")
+        );
+        assert_eq!(
+            "d0 >= 5".parse::<Check>().unwrap_err().code(),
+            ErrorCode::new(502, "check-tokens-invalid")
+        );
+    }
+
+    #[test]
+    fn chance_works() {
+        assert_close(check("d20 + 7 >= 15").chance().unwrap(), 13.0 / 20.0);
+        assert_close(check("d20 + 7 > 15").chance().unwrap(), 12.0 / 20.0);
+        assert_close(check("2d20:adv >= 20").chance().unwrap(), 39.0 / 400.0);
+        assert_close(check("2d6 <= 3").chance().unwrap(), 3.0 / 36.0);
+        assert_close(check("2d6 < 2").chance().unwrap(), 0.0);
+    }
+
+    #[test]
+    fn estimate_matches_chance() {
+        let check = check("2d20:adv + 7 >= 15");
+        let estimate = check.estimate(&check.tokens().simulate(20_000, 7));
+        let chance = check.chance().unwrap();
+        assert!(
+            estimate.low < chance && chance < estimate.high,
+            "{estimate:?}"
+        );
+    }
+
+    #[test]
+    fn naturals_works() {
+        let naturals = check("d20 + 7 >= 30").naturals().unwrap();
+        assert_close(naturals.twenty, 1.0 / 20.0);
+        assert_close(naturals.one, 1.0 / 20.0);
+        assert_close(naturals.chance, 1.0 / 20.0);
+
+        let naturals = check("2d20:adv + 7 >= 2").naturals().unwrap();
+        assert_close(naturals.twenty, 39.0 / 400.0);
+        assert_close(naturals.one, 1.0 / 400.0);
+        assert_close(naturals.chance, 399.0 / 400.0);
+
+        assert_eq!(check("2d20 >= 15").naturals(), None);
+        assert_eq!(check("d20 + d20 >= 15").naturals(), None);
+        assert_eq!(check("d20 <= 15").naturals(), None);
+    }
+}
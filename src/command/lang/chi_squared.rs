@@ -0,0 +1,156 @@
+use std::f64::consts::PI;
+
+/// Coefficients of the Lanczos approximation of the gamma function, with `g = 7`.
+const LANCZOS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+const EPSILON: f64 = 1e-15;
+const TINY: f64 = 1e-300;
+const MAX_ITERATIONS: usize = 1_000;
+
+/// Expected count of every face below which the test is not reliable.
+pub const MIN_EXPECTED: f64 = 5.0;
+
+/// Result of a chi-squared goodness-of-fit test against a uniform distribution.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChiSquared {
+    pub statistic: f64,
+    pub degrees_of_freedom: usize,
+    /// Probability of a fair die deviating at least as much.
+    pub p_value: f64,
+}
+
+impl ChiSquared {
+    /// Test whether every face was equally likely to get its count, `None` without at least two
+    /// faces and one roll.
+    pub fn uniform(counts: &[u64]) -> Option<Self> {
+        let total = counts.iter().sum::<u64>();
+        if counts.len() < 2 || total == 0 {
+            return None;
+        }
+
+        let expected = total as f64 / counts.len() as f64;
+        let statistic = counts
+            .iter()
+            .map(|count| (*count as f64 - expected).powi(2) / expected)
+            .sum::<f64>();
+        let degrees_of_freedom = counts.len() - 1;
+
+        Some(Self {
+            statistic,
+            degrees_of_freedom,
+            p_value: upper_gamma(degrees_of_freedom as f64 / 2.0, statistic / 2.0),
+        })
+    }
+}
+
+fn ln_gamma(x: f64) -> f64 {
+    if x < 0.5 {
+        return (PI / (PI * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+
+    let x = x - 1.0;
+    let sum = LANCZOS[1..]
+        .iter()
+        .enumerate()
+        .fold(LANCZOS[0], |sum, (i, c)| sum + c / (x + i as f64 + 1.0));
+    let t = x + 7.5;
+    0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// Regularized upper incomplete gamma function `Q(a, x)`.
+///
+/// Uses the series of `P(a, x)` below `a + 1`, where it converges fast, and a continued fraction
+/// above it.
+fn upper_gamma(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+    let prefix = (-x + a * x.ln() - ln_gamma(a)).exp();
+
+    if x < a + 1.0 {
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        for n in 1..MAX_ITERATIONS {
+            term *= x / (a + n as f64);
+            sum += term;
+            if term.abs() < sum.abs() * EPSILON {
+                break;
+            }
+        }
+        (1.0 - sum * prefix).clamp(0.0, 1.0)
+    } else {
+        // Modified Lentz's method
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / TINY;
+        let mut d = 1.0 / b;
+        let mut fraction = d;
+        for n in 1..MAX_ITERATIONS {
+            let an = -(n as f64) * (n as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = b + an / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            fraction *= delta;
+            if (delta - 1.0).abs() < EPSILON {
+                break;
+            }
+        }
+        (fraction * prefix).clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::command::lang::assert_close;
+
+    #[test]
+    fn ln_gamma_works() {
+        assert_close(ln_gamma(1.0), 0.0);
+        assert_close(ln_gamma(5.0), 24f64.ln());
+        assert_close(ln_gamma(0.5), PI.sqrt().ln());
+        assert_close(ln_gamma(10.5), 1133278.3889487855f64.ln());
+    }
+
+    #[test]
+    fn upper_gamma_works() {
+        // Two degrees of freedom have a closed form
+        for x in [0.5, 2.0, 4.0, 30.0] {
+            assert_close(upper_gamma(1.0, x / 2.0), (-x / 2.0).exp());
+        }
+        // Critical values at 5%
+        assert_close(upper_gamma(0.5, 3.841_458_820_694_124 / 2.0), 0.05);
+        assert_close(upper_gamma(2.5, 11.070_497_693_516_351 / 2.0), 0.05);
+        assert_close(upper_gamma(9.5, 30.143_527_205_646_16 / 2.0), 0.05);
+    }
+
+    #[test]
+    fn uniform_works() {
+        let fair = ChiSquared::uniform(&[10; 20]).unwrap();
+        assert_eq!((fair.statistic, fair.degrees_of_freedom), (0.0, 19));
+        assert_close(fair.p_value, 1.0);
+
+        let loaded = ChiSquared::uniform(&[5, 5, 5, 5, 5, 35]).unwrap();
+        assert_close(loaded.statistic, 75.0);
+        assert!(loaded.p_value < 1e-10, "{loaded:?}");
+
+        assert_eq!(ChiSquared::uniform(&[10]), None);
+        assert_eq!(ChiSquared::uniform(&[0, 0]), None);
+    }
+}
@@ -0,0 +1,138 @@
+use std::{
+    fmt::Display,
+    num::{IntErrorKind, ParseIntError},
+};
+
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+
+/// Stable identifier of an error that other tools can match on instead of the message.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ErrorCode {
+    pub number: u16,
+    pub name: &'static str,
+}
+
+impl ErrorCode {
+    pub const fn new(number: u16, name: &'static str) -> Self {
+        Self { number, name }
+    }
+
+    pub fn id(&self) -> String {
+        format!("E{:04}", self.number)
+    }
+}
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.id(), self.name)
+    }
+}
+
+pub trait Diagnostic: Display {
+    fn code(&self) -> ErrorCode;
+
+    /// Part of the input the error is about.
+    fn token(&self) -> Option<&str> {
+        None
+    }
+
+    /// Error this one was caused by.
+    fn cause(&self) -> Option<&dyn Diagnostic> {
+        None
+    }
+
+    /// The most specific error in the chain.
+    fn root_cause(&self) -> &dyn Diagnostic
+    where
+        Self: Sized,
+    {
+        let mut error: &dyn Diagnostic = self;
+        while let Some(cause) = error.cause() {
+            error = cause;
+        }
+        error
+    }
+}
+
+impl Serialize for dyn Diagnostic + '_ {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let code = self.code();
+        let mut error = serializer.serialize_struct("Diagnostic", 5)?;
+        error.serialize_field("code", &code.id())?;
+        error.serialize_field("name", code.name)?;
+        error.serialize_field("token", &self.token())?;
+        error.serialize_field("message", &self.to_string())?;
+        error.serialize_field("cause", &self.cause())?;
+        error.end()
+    }
+}
+
+impl Diagnostic for ParseIntError {
+    fn code(&self) -> ErrorCode {
+        match self.kind() {
+            IntErrorKind::Empty => ErrorCode::new(1, "number-empty"),
+            IntErrorKind::InvalidDigit => ErrorCode::new(2, "number-invalid-digit"),
+            IntErrorKind::PosOverflow => ErrorCode::new(3, "number-too-large"),
+            IntErrorKind::NegOverflow => ErrorCode::new(4, "number-too-small"),
+            IntErrorKind::Zero => ErrorCode::new(5, "number-zero"),
+            _ => ErrorCode::new(0, "number-invalid"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+    use crate::command::lang::{ParseTokenError, Tokens};
+
+    #[test]
+    fn code_display_works() {
+        assert_eq!(
+            ErrorCode::new(103, "modifier-take-exceeds-dice").to_string(),
+            "E0103 modifier-take-exceeds-dice"
+        );
+    }
+
+    #[test]
+    fn root_cause_works() {
+        let e = "4d6:adv20".parse::<Tokens>().unwrap_err();
+        assert_eq!(
+            e.root_cause().code(),
+            ErrorCode::new(103, "modifier-take-exceeds-dice")
+        );
+        let e = "other".parse::<Tokens>().unwrap_err();
+        assert_eq!(e.root_cause().code(), ErrorCode::new(300, "token-invalid"));
+    }
+
+    #[test]
+    fn serialize_works() {
+        let e: ParseTokenError = "2d0".parse::<Tokens>().unwrap_err();
+        assert_eq!(
+            serde_json::to_value(&e).unwrap(),
+            json!({
+                "code": "E0301",
+                "name": "token-die-invalid",
+                "token": "2d0",
+                "message": "Error parsing `2d0`.\nDie: Error parsing `0`.\nSides: number would be zero for non-zero type",
+                "cause": {
+                    "code": "E0201",
+                    "name": "die-sides-invalid",
+                    "token": "0",
+                    "message": "Error parsing `0`.\nSides: number would be zero for non-zero type",
+                    "cause": {
+                        "code": "E0005",
+                        "name": "number-zero",
+                        "token": null,
+                        "message": "number would be zero for non-zero type",
+                        "cause": null,
+                    },
+                },
+            })
+        );
+    }
+}
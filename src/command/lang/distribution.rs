@@ -0,0 +1,402 @@
+use std::num::NonZeroU8;
+
+use super::{Dice, Modifier, Token, Tokens};
+
+/// Rough number of operations an exact distribution is allowed to take.
+pub const MAX_EXACT_COST: u64 = 200_000_000;
+
+/// Probability of every possible total of a roll.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Distribution {
+    min: i32,
+    /// Probability of `min + i` at `i`.
+    probabilities: Vec<f64>,
+}
+
+impl Distribution {
+    pub fn constant(value: i32) -> Self {
+        Self {
+            min: value,
+            probabilities: vec![1.0],
+        }
+    }
+
+    pub fn die(sides: NonZeroU8) -> Self {
+        let sides = u8::from(sides) as usize;
+        Self {
+            min: 1,
+            probabilities: vec![1.0 / sides as f64; sides],
+        }
+    }
+
+    /// Share of every total in sorted `totals`.
+    pub fn from_samples(totals: &[i32]) -> Self {
+        let min = totals[0];
+        let mut probabilities = vec![0.0; (totals[totals.len() - 1] - min) as usize + 1];
+        for total in totals {
+            probabilities[(total - min) as usize] += 1.0 / totals.len() as f64;
+        }
+
+        Self { min, probabilities }
+    }
+
+    /// Distribution of the sum of both rolls.
+    pub fn add(&self, other: &Self) -> Self {
+        let mut probabilities = vec![0.0; self.probabilities.len() + other.probabilities.len() - 1];
+        for (i, a) in self.probabilities.iter().enumerate() {
+            for (j, b) in other.probabilities.iter().enumerate() {
+                probabilities[i + j] += a * b;
+            }
+        }
+
+        Self {
+            min: self.min + other.min,
+            probabilities,
+        }
+    }
+
+    /// Distribution of the sum of the dice kept by the modifier.
+    ///
+    /// Faces are visited from the best to the worst for the modifier, the first `take` dice to
+    /// land on them are the kept ones. Once all of them are placed, the remaining dice can only
+    /// land on worse faces, which doesn't change the total.
+    pub fn keep(dice: Dice, modifier: &Modifier) -> Self {
+        let count = u8::from(dice.count()) as usize;
+        let sides = u8::from(dice.sides()) as usize;
+        let (take, faces): (_, Box<dyn Iterator<Item = usize>>) = match modifier {
+            Modifier::Advantage(take) => (u8::from(*take) as usize, Box::new((1..=sides).rev())),
+            Modifier::Disadvantage(take) => (u8::from(*take) as usize, Box::new(1..=sides)),
+        };
+        let take = take.min(count);
+
+        let mut binomials = vec![vec![1.0; 1]; count + 1];
+        for n in 1..=count {
+            binomials[n] = (0..=n)
+                .map(|k| {
+                    if k == 0 || k == n {
+                        1.0
+                    } else {
+                        binomials[n - 1][k - 1] + binomials[n - 1][k]
+                    }
+                })
+                .collect();
+        }
+        let face = 1.0 / sides as f64;
+
+        // `placed[m][sum]` - probability of `m` dice being on visited faces with kept `sum`
+        let mut placed = vec![vec![0.0; take * sides + 1]; take];
+        placed[0][0] = 1.0;
+        let mut result = vec![0.0; take * sides + 1];
+
+        for (visited, value) in faces.enumerate() {
+            let remaining_faces = (sides - visited - 1) as f64 * face;
+            let mut next = vec![vec![0.0; take * sides + 1]; take];
+            for (m, sums) in placed.iter().enumerate() {
+                for (sum, &p) in sums.iter().enumerate().filter(|(_, p)| **p > 0.0) {
+                    let left = count - m;
+                    for j in 0..=left {
+                        let p = p * binomials[left][j] * face.powi(j as i32);
+                        let sum = sum + value * j.min(take - m);
+                        if m + j < take {
+                            next[m + j][sum] += p;
+                        } else {
+                            result[sum] += p * remaining_faces.powi((left - j) as i32);
+                        }
+                    }
+                }
+            }
+            placed = next;
+        }
+
+        Self {
+            min: take as i32,
+            probabilities: result.split_off(take),
+        }
+    }
+
+    pub fn min(&self) -> i32 {
+        self.min
+    }
+
+    pub fn max(&self) -> i32 {
+        self.min + self.probabilities.len() as i32 - 1
+    }
+
+    /// Every total with its probability.
+    pub fn iter(&self) -> impl Iterator<Item = (i32, f64)> + '_ {
+        self.probabilities
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (self.min + i as i32, *p))
+    }
+
+    /// Totals grouped into at most `count` ranges of the same width, with their probability.
+    pub fn buckets(&self, count: usize) -> Vec<(String, f64)> {
+        self.buckets_between(self.min, self.max(), count)
+    }
+
+    /// Totals from `min` to `max` grouped into at most `count` ranges of the same width, with
+    /// their probability, so that distributions can be bucketed alike.
+    pub fn buckets_between(&self, min: i32, max: i32, count: usize) -> Vec<(String, f64)> {
+        let width = ((max - min + 1) as usize).div_ceil(count.max(1)) as i32;
+        (min..=max)
+            .step_by(width as usize)
+            .map(|from| {
+                let to = (from + width - 1).min(max);
+                let label = if from == to {
+                    from.to_string()
+                } else {
+                    format!("{from}..{to}")
+                };
+                (
+                    label,
+                    (from..=to).map(|value| self.probability(value)).sum(),
+                )
+            })
+            .collect()
+    }
+
+    pub fn probability(&self, value: i32) -> f64 {
+        usize::try_from(value - self.min)
+            .ok()
+            .and_then(|i| self.probabilities.get(i))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Probability of rolling a higher total than `other`.
+    pub fn beats(&self, other: &Self) -> f64 {
+        let mut below = 0.0;
+        let mut others = other.iter().peekable();
+        self.iter()
+            .map(|(value, p)| {
+                while let Some((_, q)) = others.next_if(|(other, _)| *other < value) {
+                    below += q;
+                }
+                p * below
+            })
+            .sum()
+    }
+
+    /// Probability of rolling `value` or less.
+    pub fn cumulative(&self, value: i32) -> f64 {
+        self.iter()
+            .take_while(|(total, _)| *total <= value)
+            .map(|(_, p)| p)
+            .sum()
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.iter().map(|(value, p)| value as f64 * p).sum()
+    }
+
+    pub fn std_dev(&self) -> f64 {
+        let mean = self.mean();
+        self.iter()
+            .map(|(value, p)| (value as f64 - mean).powi(2) * p)
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    /// Smallest total that is rolled at least half of the time.
+    pub fn median(&self) -> i32 {
+        let mut cumulative = 0.0;
+        for (value, p) in self.iter() {
+            cumulative += p;
+            if cumulative >= 0.5 - 1e-9 {
+                return value;
+            }
+        }
+        self.max()
+    }
+}
+
+impl Token {
+    /// Rough number of operations [`Token::distribution`] takes.
+    pub fn exact_cost(&self) -> u64 {
+        match self {
+            Token::Die(dice, modifier) => {
+                let count = u8::from(dice.count()) as u64;
+                let sides = u8::from(dice.sides()) as u64;
+                match modifier {
+                    Some(Modifier::Advantage(take) | Modifier::Disadvantage(take)) => {
+                        let take = u8::from(*take) as u64;
+                        sides * sides * take * take * count
+                    }
+                    None => count * count * sides * sides,
+                }
+            }
+            Token::Constant(_) => 1,
+        }
+    }
+
+    pub fn distribution(&self) -> Distribution {
+        match self {
+            Token::Die(dice, Some(modifier)) => Distribution::keep(*dice, modifier),
+            Token::Die(dice, None) => {
+                let die = Distribution::die(dice.sides());
+                (1..u8::from(dice.count())).fold(die.clone(), |sum, _| sum.add(&die))
+            }
+            Token::Constant(value) => Distribution::constant(i16::from(*value) as i32),
+        }
+    }
+}
+
+impl Tokens {
+    /// Rough number of operations [`Tokens::distribution`] takes.
+    pub fn exact_cost(&self) -> u64 {
+        self.0.iter().map(Token::exact_cost).sum()
+    }
+
+    /// Exact distribution of the total, `None` if it is too expensive to compute.
+    pub fn distribution(&self) -> Option<Distribution> {
+        if self.exact_cost() > MAX_EXACT_COST {
+            return None;
+        }
+
+        Some(
+            self.0
+                .iter()
+                .map(Token::distribution)
+                .fold(Distribution::constant(0), |sum, token| sum.add(&token)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::command::lang::assert_close;
+
+    fn distribution(s: &str) -> Distribution {
+        s.parse::<Tokens>().unwrap().distribution().unwrap()
+    }
+
+    fn assert_sums_to_one(distribution: &Distribution) {
+        assert_close(distribution.iter().map(|(_, p)| p).sum(), 1.0);
+    }
+
+    #[test]
+    fn distribution_d6_works() {
+        let d6 = distribution("d6");
+        assert_eq!((d6.min(), d6.max()), (1, 6));
+        for value in 1..=6 {
+            assert_close(d6.probability(value), 1.0 / 6.0);
+        }
+        assert_close(d6.mean(), 3.5);
+    }
+
+    #[test]
+    fn distribution_2d6_works() {
+        let two_d6 = distribution("2d6 + 3");
+        assert_eq!((two_d6.min(), two_d6.max()), (5, 15));
+        assert_close(two_d6.probability(10), 6.0 / 36.0);
+        assert_close(two_d6.probability(5), 1.0 / 36.0);
+        assert_close(two_d6.mean(), 10.0);
+        assert_close(two_d6.std_dev(), (35.0f64 / 6.0).sqrt());
+        assert_eq!(two_d6.median(), 10);
+        assert_sums_to_one(&two_d6);
+    }
+
+    #[test]
+    fn distribution_3d6_median_works() {
+        assert_eq!(distribution("3d6").median(), 10);
+        assert_eq!(distribution("3d6 + 1").median(), 11);
+    }
+
+    #[test]
+    fn distribution_advantage_works() {
+        let advantage = distribution("2d20:adv");
+        assert_eq!((advantage.min(), advantage.max()), (1, 20));
+        assert_close(advantage.probability(20), 39.0 / 400.0);
+        assert_close(advantage.probability(1), 1.0 / 400.0);
+        assert_close(advantage.mean(), 13.825);
+        assert_sums_to_one(&advantage);
+    }
+
+    #[test]
+    fn distribution_disadvantage_works() {
+        let disadvantage = distribution("2d20:dis");
+        assert_close(disadvantage.probability(1), 39.0 / 400.0);
+        assert_close(disadvantage.probability(20), 1.0 / 400.0);
+        assert_close(disadvantage.mean(), 7.175);
+        assert_sums_to_one(&disadvantage);
+    }
+
+    #[test]
+    fn distribution_4d6_drop_lowest_works() {
+        let stats = distribution("4d6:adv3");
+        assert_eq!((stats.min(), stats.max()), (3, 18));
+        assert_close(stats.probability(18), 21.0 / 1296.0);
+        assert_close(stats.probability(3), 1.0 / 1296.0);
+        assert_close(stats.mean(), 15869.0 / 1296.0);
+        assert_sums_to_one(&stats);
+    }
+
+    #[test]
+    fn distribution_keep_all_matches_sum() {
+        let kept = distribution("3d8:adv3");
+        let summed = distribution("3d8");
+        for (value, p) in summed.iter() {
+            assert_close(kept.probability(value), p);
+        }
+    }
+
+    #[test]
+    fn buckets_works() {
+        let buckets = distribution("2d6").buckets(6);
+        assert_eq!(
+            buckets
+                .iter()
+                .map(|(label, _)| label.as_str())
+                .collect::<Vec<_>>(),
+            ["2..3", "4..5", "6..7", "8..9", "10..11", "12"]
+        );
+        assert_close(buckets[0].1, 3.0 / 36.0);
+        assert_close(buckets[2].1, 11.0 / 36.0);
+        assert_close(buckets[5].1, 1.0 / 36.0);
+
+        assert_eq!(distribution("d4").buckets(20).len(), 4);
+    }
+
+    #[test]
+    fn buckets_between_works() {
+        let buckets = distribution("d4").buckets_between(0, 7, 3);
+        assert_eq!(
+            buckets
+                .iter()
+                .map(|(label, _)| label.as_str())
+                .collect::<Vec<_>>(),
+            ["0..2", "3..5", "6..7"]
+        );
+        assert_close(buckets[0].1, 2.0 / 4.0);
+        assert_close(buckets[1].1, 2.0 / 4.0);
+        assert_close(buckets[2].1, 0.0);
+    }
+
+    #[test]
+    fn cumulative_works() {
+        let two_d6 = distribution("2d6");
+        assert_close(two_d6.cumulative(1), 0.0);
+        assert_close(two_d6.cumulative(3), 3.0 / 36.0);
+        assert_close(two_d6.cumulative(7), 21.0 / 36.0);
+        assert_close(two_d6.cumulative(20), 1.0);
+    }
+
+    #[test]
+    fn beats_works() {
+        let d6 = distribution("d6");
+        assert_close(d6.beats(&d6), 15.0 / 36.0);
+        assert_close(distribution("d6 + 6").beats(&d6), 1.0);
+        assert_close(d6.beats(&distribution("d6 + 6")), 0.0);
+
+        let (two_d6, d12) = (distribution("2d6 + 3"), distribution("d12 + 4"));
+        let ties = 1.0 - two_d6.beats(&d12) - d12.beats(&two_d6);
+        assert_close(ties, 36.0 / 432.0);
+    }
+
+    #[test]
+    fn distribution_too_expensive_fails() {
+        assert_eq!("100d255".parse::<Tokens>().unwrap().distribution(), None);
+    }
+}
@@ -3,6 +3,11 @@ use std::{
     fmt::{Debug, Display},
 };
 
+use serde::{Serialize, Serializer};
+
+use super::diagnostic::{Diagnostic, ErrorCode};
+use crate::command::locale::{Locale, Localize};
+
 #[derive(Debug)]
 pub struct ParseError<Kind>
 where
@@ -21,12 +26,29 @@ where
     }
 }
 
+impl<Kind> Localize for ParseError<Kind>
+where
+    Kind: Debug + Display + Localize,
+{
+    fn localize(&self, locale: Locale) -> String {
+        locale
+            .catalog()
+            .parse_error(&self.token, &self.kind.localize(locale))
+    }
+}
+
 impl<Kind> Display for ParseError<Kind>
 where
     Kind: Debug + Display,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Error parsing `{}`.\n{}", self.token, self.kind)
+        write!(
+            f,
+            "{}",
+            Locale::English
+                .catalog()
+                .parse_error(&self.token, &self.kind.to_string())
+        )
     }
 }
 
@@ -40,3 +62,32 @@ where
         self.token == other.token && self.kind == other.kind
     }
 }
+
+impl<Kind> Diagnostic for ParseError<Kind>
+where
+    Kind: Debug + Display + Diagnostic,
+{
+    fn code(&self) -> ErrorCode {
+        self.kind.code()
+    }
+
+    fn token(&self) -> Option<&str> {
+        Some(&self.token)
+    }
+
+    fn cause(&self) -> Option<&dyn Diagnostic> {
+        self.kind.cause()
+    }
+}
+
+impl<Kind> Serialize for ParseError<Kind>
+where
+    Kind: Debug + Display + Diagnostic,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (self as &dyn Diagnostic).serialize(serializer)
+    }
+}
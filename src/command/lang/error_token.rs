@@ -3,7 +3,11 @@ use std::{
     num::{NonZeroU8, ParseIntError},
 };
 
-use super::error::ParseError;
+use super::{
+    diagnostic::{Diagnostic, ErrorCode},
+    error::ParseError,
+};
+use crate::command::locale::{Locale, Localize};
 
 pub type ParseNumberError = ParseError<ParseIntError>;
 
@@ -13,17 +17,38 @@ pub enum ModifierCountError {
     MoreThanDice { take: NonZeroU8, count: NonZeroU8 },
 }
 
+impl Localize for ModifierCountError {
+    fn localize(&self, locale: Locale) -> String {
+        match self {
+            ModifierCountError::Parse(e) => e.localize(locale),
+            ModifierCountError::MoreThanDice { take, count } => locale
+                .catalog()
+                .modifier_more_than_dice(u8::from(*take), u8::from(*count)),
+        }
+    }
+}
+
 impl Display for ModifierCountError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                ModifierCountError::Parse(e) => format!("{e}"),
-                ModifierCountError::MoreThanDice { take, count } =>
-                    format!("dice to leave ({take}) cannot be more than dice thrown ({count})"),
+        write!(f, "{}", self.localize(Locale::English))
+    }
+}
+
+impl Diagnostic for ModifierCountError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            ModifierCountError::Parse(_) => ErrorCode::new(104, "modifier-take-invalid"),
+            ModifierCountError::MoreThanDice { .. } => {
+                ErrorCode::new(103, "modifier-take-exceeds-dice")
             }
-        )
+        }
+    }
+
+    fn cause(&self) -> Option<&dyn Diagnostic> {
+        match self {
+            ModifierCountError::Parse(e) => Some(e),
+            ModifierCountError::MoreThanDice { .. } => None,
+        }
     }
 }
 
@@ -34,17 +59,41 @@ pub enum ModifierErrorKind {
     Disadvantage(ModifierCountError),
 }
 
+impl Localize for ModifierErrorKind {
+    fn localize(&self, locale: Locale) -> String {
+        let catalog = locale.catalog();
+        match self {
+            ModifierErrorKind::Invalid => catalog.modifier_invalid(),
+            ModifierErrorKind::Advantage(e) => catalog.modifier_advantage(&e.localize(locale)),
+            ModifierErrorKind::Disadvantage(e) => {
+                catalog.modifier_disadvantage(&e.localize(locale))
+            }
+        }
+    }
+}
+
 impl Display for ModifierErrorKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                ModifierErrorKind::Invalid => "Invalid modifier".to_string(),
-                ModifierErrorKind::Advantage(e) => format!("Advantage count: {e}"),
-                ModifierErrorKind::Disadvantage(e) => format!("Disadvantage count: {e}"),
+        write!(f, "{}", self.localize(Locale::English))
+    }
+}
+
+impl Diagnostic for ModifierErrorKind {
+    fn code(&self) -> ErrorCode {
+        match self {
+            ModifierErrorKind::Invalid => ErrorCode::new(100, "modifier-invalid"),
+            ModifierErrorKind::Advantage(_) => ErrorCode::new(101, "modifier-advantage-invalid"),
+            ModifierErrorKind::Disadvantage(_) => {
+                ErrorCode::new(102, "modifier-disadvantage-invalid")
             }
-        )
+        }
+    }
+
+    fn cause(&self) -> Option<&dyn Diagnostic> {
+        match self {
+            ModifierErrorKind::Invalid => None,
+            ModifierErrorKind::Advantage(e) | ModifierErrorKind::Disadvantage(e) => Some(e),
+        }
     }
 }
 
@@ -57,17 +106,37 @@ pub enum DieErrorKind {
     Modifier(ParseModifierError),
 }
 
+impl Localize for DieErrorKind {
+    fn localize(&self, locale: Locale) -> String {
+        let catalog = locale.catalog();
+        match self {
+            DieErrorKind::Count(e) => catalog.die_count(&e.localize(locale)),
+            DieErrorKind::Sides(e) => catalog.die_sides(&e.localize(locale)),
+            DieErrorKind::Modifier(e) => catalog.die_modifier(&e.localize(locale)),
+        }
+    }
+}
+
 impl Display for DieErrorKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                DieErrorKind::Count(e) => format!("Count: {e}"),
-                DieErrorKind::Sides(e) => format!("Sides: {e}"),
-                DieErrorKind::Modifier(e) => format!("Modifier: {e}"),
-            }
-        )
+        write!(f, "{}", self.localize(Locale::English))
+    }
+}
+
+impl Diagnostic for DieErrorKind {
+    fn code(&self) -> ErrorCode {
+        match self {
+            DieErrorKind::Count(_) => ErrorCode::new(200, "die-count-invalid"),
+            DieErrorKind::Sides(_) => ErrorCode::new(201, "die-sides-invalid"),
+            DieErrorKind::Modifier(_) => ErrorCode::new(202, "die-modifier-invalid"),
+        }
+    }
+
+    fn cause(&self) -> Option<&dyn Diagnostic> {
+        match self {
+            DieErrorKind::Count(e) | DieErrorKind::Sides(e) => Some(e),
+            DieErrorKind::Modifier(e) => Some(e),
+        }
     }
 }
 
@@ -78,21 +147,52 @@ pub type ParseConstantError = ParseNumberError;
 #[derive(Debug, PartialEq)]
 pub enum TokenErrorKind {
     Invalid,
+    /// A die after a minus sign, like `2d6 - d4`.
+    Subtracted,
+    /// A constant right before a die without a count, like `d20 5 d6`.
+    Ambiguous,
     Die(ParseDieError),
     Constant(ParseConstantError),
 }
 
+impl Localize for TokenErrorKind {
+    fn localize(&self, locale: Locale) -> String {
+        let catalog = locale.catalog();
+        match self {
+            TokenErrorKind::Invalid => catalog.token_invalid(),
+            TokenErrorKind::Subtracted => catalog.token_subtracted(),
+            TokenErrorKind::Ambiguous => catalog.token_ambiguous(),
+            TokenErrorKind::Die(e) => catalog.token_die(&e.localize(locale)),
+            TokenErrorKind::Constant(e) => catalog.token_constant(&e.localize(locale)),
+        }
+    }
+}
+
 impl Display for TokenErrorKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                TokenErrorKind::Invalid => "Invalid token".to_string(),
-                TokenErrorKind::Die(e) => format!("Die: {e}"),
-                TokenErrorKind::Constant(e) => format!("Constant: {e}"),
+        write!(f, "{}", self.localize(Locale::English))
+    }
+}
+
+impl Diagnostic for TokenErrorKind {
+    fn code(&self) -> ErrorCode {
+        match self {
+            TokenErrorKind::Invalid => ErrorCode::new(300, "token-invalid"),
+            TokenErrorKind::Die(_) => ErrorCode::new(301, "token-die-invalid"),
+            TokenErrorKind::Constant(_) => ErrorCode::new(302, "token-constant-invalid"),
+            TokenErrorKind::Subtracted => ErrorCode::new(303, "token-die-subtracted"),
+            TokenErrorKind::Ambiguous => ErrorCode::new(304, "token-count-ambiguous"),
+        }
+    }
+
+    fn cause(&self) -> Option<&dyn Diagnostic> {
+        match self {
+            TokenErrorKind::Invalid | TokenErrorKind::Subtracted | TokenErrorKind::Ambiguous => {
+                None
             }
-        )
+            TokenErrorKind::Die(e) => Some(e),
+            TokenErrorKind::Constant(e) => Some(e),
+        }
     }
 }
 
@@ -0,0 +1,247 @@
+use std::{borrow::Cow, error::Error, fmt::Display};
+
+use serde::{Serialize, Serializer};
+
+use super::{
+    diagnostic::{Diagnostic, ErrorCode},
+    Token, Tokens, Versus,
+};
+use crate::command::{
+    locale::{Locale, Localize},
+    table::strip_ansi,
+};
+
+/// Caps on how much a single roll is allowed to do.
+#[derive(Debug, Clone)]
+pub struct Limits {
+    /// Dice rolled across all tokens.
+    pub dice: u16,
+    /// Tokens in one expression.
+    pub tokens: u8,
+    /// Characters in the rendered reply.
+    pub output_length: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            dice: 100,
+            tokens: 20,
+            // Discord refuses messages longer than this
+            output_length: 2000,
+        }
+    }
+}
+
+impl Limits {
+    pub fn check_output(&self, output: &str) -> Result<(), LimitError> {
+        let length = output.chars().count();
+        if length > self.output_length {
+            Err(LimitError::OutputLength {
+                length,
+                max: self.output_length,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// `output` as it fits in a message, without the colours of ```ansi blocks when only their
+    /// escape codes make it too long.
+    pub fn fit_output<'a>(&self, output: &'a str) -> Result<Cow<'a, str>, LimitError> {
+        self.check_output(output)
+            .map(|()| Cow::Borrowed(output))
+            .or_else(|_| {
+                let plain = strip_ansi(output);
+                self.check_output(&plain)?;
+                Ok(plain)
+            })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum LimitError {
+    Dice { count: usize, max: u16 },
+    Tokens { count: usize, max: u8 },
+    OutputLength { length: usize, max: usize },
+}
+
+impl Localize for LimitError {
+    fn localize(&self, locale: Locale) -> String {
+        let catalog = locale.catalog();
+        match self {
+            LimitError::Dice { count, max } => catalog.limit_dice(*count, *max),
+            LimitError::Tokens { count, max } => catalog.limit_tokens(*count, *max),
+            LimitError::OutputLength { length, max } => catalog.limit_output_length(*length, *max),
+        }
+    }
+}
+
+impl Display for LimitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.localize(Locale::English))
+    }
+}
+
+impl Error for LimitError {}
+
+impl Diagnostic for LimitError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            LimitError::Dice { .. } => ErrorCode::new(400, "limit-dice"),
+            LimitError::Tokens { .. } => ErrorCode::new(401, "limit-tokens"),
+            LimitError::OutputLength { .. } => ErrorCode::new(402, "limit-output-length"),
+        }
+    }
+}
+
+impl Serialize for LimitError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (self as &dyn Diagnostic).serialize(serializer)
+    }
+}
+
+impl Tokens {
+    pub fn check_limits(&self, limits: &Limits) -> Result<(), LimitError> {
+        if self.0.len() > limits.tokens as usize {
+            return Err(LimitError::Tokens {
+                count: self.0.len(),
+                max: limits.tokens,
+            });
+        }
+
+        check_dice(self.dice_count(), limits)
+    }
+
+    fn dice_count(&self) -> usize {
+        self.0
+            .iter()
+            .map(|token| match token {
+                Token::Die(dice, _) => u8::from(dice.count()) as usize,
+                Token::Constant(_) => 0,
+            })
+            .sum()
+    }
+}
+
+impl Versus {
+    /// Limits of each side, with the dice of both sides counted together since both are rolled.
+    pub fn check_limits(&self, limits: &Limits) -> Result<(), LimitError> {
+        self.left().check_limits(limits)?;
+        self.right().check_limits(limits)?;
+        check_dice(self.left().dice_count() + self.right().dice_count(), limits)
+    }
+}
+
+fn check_dice(count: usize, limits: &Limits) -> Result<(), LimitError> {
+    if count > limits.dice as usize {
+        Err(LimitError::Dice {
+            count,
+            max: limits.dice,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn check_limits_works() {
+        assert_eq!(
+            "60d6 40d4 + 5"
+                .parse::<Tokens>()
+                .unwrap()
+                .check_limits(&Limits::default()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn check_limits_dice_fails() {
+        assert_eq!(
+            "60d6 41d4"
+                .parse::<Tokens>()
+                .unwrap()
+                .check_limits(&Limits::default()),
+            Err(LimitError::Dice {
+                count: 101,
+                max: 100
+            })
+        );
+    }
+
+    #[test]
+    fn check_limits_tokens_fails() {
+        assert_eq!(
+            "1 + 1 + 1"
+                .parse::<Tokens>()
+                .unwrap()
+                .check_limits(&Limits {
+                    tokens: 2,
+                    ..Default::default()
+                }),
+            Err(LimitError::Tokens { count: 3, max: 2 })
+        );
+    }
+
+    #[test]
+    fn check_limits_versus_counts_both_sides() {
+        let versus = |s: &str| {
+            s.parse::<Versus>()
+                .unwrap()
+                .check_limits(&Limits::default())
+        };
+        assert_eq!(versus("50d6 vs 50d6"), Ok(()));
+        assert_eq!(
+            versus("60d6 vs 50d6 + 3"),
+            Err(LimitError::Dice {
+                count: 110,
+                max: 100
+            })
+        );
+    }
+
+    #[test]
+    fn fit_output_drops_colours_then_fails() {
+        let limits = Limits::default();
+        assert_eq!(limits.fit_output("20"), Ok(Cow::Borrowed("20")));
+
+        // Escape codes count, so these 2200 characters only fit without them
+        let coloured = "\u{1b}[32m20\u{1b}[0m".repeat(200);
+        assert_eq!(
+            limits.check_output(&coloured),
+            Err(LimitError::OutputLength {
+                length: 2200,
+                max: 2000
+            })
+        );
+        assert_eq!(limits.fit_output(&coloured).unwrap(), "20".repeat(200));
+
+        // Too long even without colours, so it gets attached
+        let coloured = "\u{1b}[32m20\u{1b}[0m".repeat(1001);
+        assert_eq!(
+            limits.fit_output(&coloured),
+            Err(LimitError::OutputLength {
+                length: 2002,
+                max: 2000
+            })
+        );
+    }
+
+    #[test]
+    fn check_output_fails() {
+        assert_eq!(
+            Limits::default().check_output(&"-".repeat(2001)),
+            Err(LimitError::OutputLength {
+                length: 2001,
+                max: 2000
+            })
+        );
+    }
+}
@@ -1,25 +1,66 @@
+mod check;
+mod chi_squared;
+mod diagnostic;
+mod distribution;
 mod error;
 mod error_token;
+mod limits;
+mod normalize;
 mod parse;
+mod rng;
+mod simulation;
+mod versus;
 
 use std::{
     fmt::Display,
     num::{NonZeroI16, NonZeroU8},
 };
 
+use itertools::Itertools;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 
+pub use check::{Check, ParseCheckError};
+pub use chi_squared::{ChiSquared, MIN_EXPECTED};
+pub use diagnostic::Diagnostic;
+pub use distribution::Distribution;
+pub use error_token::ParseTokenError;
 use error_token::*;
+pub use limits::{LimitError, Limits};
+pub use rng::{commitment, fair_rng, SeededRng};
+pub use simulation::Simulation;
+pub use versus::{ParseVersusError, Versus};
 
-use super::table::{Alignment, Table, TableRow};
+use super::{
+    locale::{Locale, Localize},
+    table::{Alignment, Cell, Colour, Span, Table, TableRow},
+};
+
+/// Face counts shown on each row of a collapsed pool.
+const FACES_PER_ROW: usize = 6;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum Modifier {
     Advantage(NonZeroU8),
     Disadvantage(NonZeroU8),
 }
 
 impl Modifier {
+    /// Whether each die counts towards the total, in the order they were rolled.
+    fn keeps(&self, dice: &[Die]) -> Vec<bool> {
+        let mut order = (0..dice.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&i| &dice[i]);
+        let take = u8::from(match self {
+            Modifier::Advantage(take) | Modifier::Disadvantage(take) => *take,
+        }) as usize;
+        let kept = match self {
+            Modifier::Advantage(_) => order.iter().rev().take(take).collect::<Vec<_>>(),
+            Modifier::Disadvantage(_) => order.iter().take(take).collect(),
+        };
+
+        (0..dice.len()).map(|i| kept.contains(&&i)).collect()
+    }
+
     fn apply(&self, dice: &[Die]) -> Vec<Die> {
         let mut sorted = dice.to_vec();
         sorted.sort();
@@ -37,31 +78,67 @@ impl Modifier {
     }
 }
 
+impl Localize for Modifier {
+    fn localize(&self, locale: Locale) -> String {
+        match self {
+            Modifier::Advantage(take) => locale.catalog().advantage(u8::from(*take)),
+            Modifier::Disadvantage(take) => locale.catalog().disadvantage(u8::from(*take)),
+        }
+    }
+}
+
 impl Display for Modifier {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Modifier::Advantage(take) => format!("Advantage (take {take})"),
-                Modifier::Disadvantage(take) => format!("Disadvantage (take {take})"),
-            }
-        )
+        write!(f, "{}", self.localize(Locale::English))
     }
 }
 
-#[derive(Debug, Eq, Clone)]
+/// Dice of the same kind thrown together, like `4d6`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Dice {
+    count: NonZeroU8,
+    sides: NonZeroU8,
+}
+
+impl Dice {
+    pub fn new(count: NonZeroU8, sides: NonZeroU8) -> Self {
+        Self { count, sides }
+    }
+
+    pub fn count(&self) -> NonZeroU8 {
+        self.count
+    }
+
+    pub fn sides(&self) -> NonZeroU8 {
+        self.sides
+    }
+
+    pub fn roll<R>(&self, rng: &mut R) -> Vec<Die>
+    where
+        R: Rng + ?Sized,
+    {
+        (0..u8::from(self.count))
+            .map(|_| Die::roll(self.sides, rng))
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct Die {
     sides: NonZeroU8,
     value: i16,
 }
 
 impl Die {
-    pub fn new(sides: NonZeroU8) -> Self {
-        Self {
-            sides,
-            value: rand::thread_rng().gen_range(1..=u8::from(sides) as i16),
-        }
+    pub fn new(sides: NonZeroU8, value: i16) -> Self {
+        Self { sides, value }
+    }
+
+    pub fn roll<R>(sides: NonZeroU8, rng: &mut R) -> Self
+    where
+        R: Rng + ?Sized,
+    {
+        Self::new(sides, rng.gen_range(1..=u8::from(sides) as i16))
     }
 
     pub fn sides(&self) -> NonZeroU8 {
@@ -71,17 +148,24 @@ impl Die {
     pub fn value(&self) -> i16 {
         self.value
     }
-}
 
-impl PartialEq for Die {
-    fn eq(&self, other: &Self) -> bool {
-        self.sides == other.sides
+    /// Green on a natural maximum, red on a natural 1.
+    pub fn colour(&self) -> Option<Colour> {
+        if self.value == i16::from(u8::from(self.sides)) {
+            Some(Colour::Green)
+        } else if self.value == 1 {
+            Some(Colour::Red)
+        } else {
+            None
+        }
     }
 }
 
 impl Ord for Die {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.value.cmp(&other.value)
+        self.value
+            .cmp(&other.value)
+            .then(self.sides.cmp(&other.sides))
     }
 }
 
@@ -91,54 +175,206 @@ impl PartialOrd for Die {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum Token {
-    Die(Vec<Die>, Option<Modifier>),
+    Die(Dice, Option<Modifier>),
     Constant(NonZeroI16),
 }
 
 impl Token {
-    fn value(&self) -> i16 {
+    pub fn roll<R>(&self, rng: &mut R) -> RolledToken
+    where
+        R: Rng + ?Sized,
+    {
         match self {
-            Token::Die(dice, modifier) => if let Some(modifier) = modifier {
-                modifier.apply(dice)
-            } else {
-                dice.clone()
-            }
-            .iter()
-            .map(Die::value)
-            .sum(),
-            Token::Constant(value) => i16::from(*value),
+            Token::Die(dice, modifier) => RolledToken::Die(dice.roll(rng), modifier.clone()),
+            Token::Constant(value) => RolledToken::Constant(*value),
         }
     }
 }
 
-impl Into<Table> for &Token {
-    fn into(self) -> Table {
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Token::Die(dice, modifier) => {
-                let mut table = Table::new(if let Some(modifier) = modifier {
-                    vec![TableRow::FullWidth(
-                        format!("// {modifier}"),
-                        Alignment::Center,
-                    )]
-                } else {
-                    vec![]
-                });
+                write!(f, "{}d{}", dice.count(), dice.sides())?;
+                match modifier {
+                    Some(Modifier::Advantage(take)) => write!(f, ":adv{take}"),
+                    Some(Modifier::Disadvantage(take)) => write!(f, ":dis{take}"),
+                    None => Ok(()),
+                }
+            }
+            Token::Constant(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Tokens(Vec<Token>);
+
+impl Display for Tokens {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, token) in self.0.iter().enumerate() {
+            match token {
+                Token::Constant(value) if i > 0 && value.is_negative() => {
+                    write!(f, " - {}", value.unsigned_abs())?
+                }
+                token if i > 0 => write!(f, " + {token}")?,
+                token => write!(f, "{token}")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Tokens {
+    /// Sides of the die when the tokens are a single bare die, like `d20` but not `4d6` or
+    /// `d20:adv`.
+    pub fn die_sides(&self) -> Option<NonZeroU8> {
+        match self.0[..] {
+            [Token::Die(dice, None)] if u8::from(dice.count()) == 1 => Some(dice.sides()),
+            _ => None,
+        }
+    }
+
+    pub fn roll<R>(&self, rng: &mut R) -> RolledTokens
+    where
+        R: Rng + ?Sized,
+    {
+        RolledTokens(self.0.iter().map(|token| token.roll(rng)).collect())
+    }
+}
+
+/// A token with its dice thrown.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum RolledToken {
+    Die(Vec<Die>, Option<Modifier>),
+    Constant(NonZeroI16),
+}
+
+impl From<&RolledToken> for Token {
+    fn from(token: &RolledToken) -> Self {
+        match token {
+            RolledToken::Die(dice, modifier) => {
+                let sides = dice
+                    .first()
+                    .expect("Die tokens always have at least one die")
+                    .sides();
+                let count = NonZeroU8::new(dice.len() as u8)
+                    .expect("Die tokens always have at least one die");
+                Token::Die(Dice::new(count, sides), modifier.clone())
+            }
+            RolledToken::Constant(value) => Token::Constant(*value),
+        }
+    }
+}
+
+/// Whether the d20 of a roll landed on its highest or lowest face.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Outcome {
+    Critical,
+    Fumble,
+}
+
+impl RolledToken {
+    /// Whether the token could have been rolled: dice of a single kind, as many as a token
+    /// holds, each showing one of its faces.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            RolledToken::Die(dice, modifier) => {
+                let Some(first) = dice.first() else {
+                    return false;
+                };
+                let take = match modifier {
+                    Some(Modifier::Advantage(take) | Modifier::Disadvantage(take)) => {
+                        usize::from(take.get())
+                    }
+                    None => 0,
+                };
+                dice.len() <= usize::from(u8::MAX)
+                    && take <= dice.len()
+                    && dice.iter().all(|die| {
+                        die.sides() == first.sides()
+                            && (1..=i16::from(die.sides().get())).contains(&die.value())
+                    })
+            }
+            RolledToken::Constant(_) => true,
+        }
+    }
+
+    /// Dice that count towards the total.
+    pub fn kept(&self) -> Vec<Die> {
+        match self {
+            RolledToken::Die(dice, Some(modifier)) => modifier.apply(dice),
+            RolledToken::Die(dice, None) => dice.clone(),
+            RolledToken::Constant(_) => vec![],
+        }
+    }
+
+    /// Every die in the order it was rolled, with whether it counts towards the total.
+    pub fn thrown(&self) -> Vec<(&Die, bool)> {
+        match self {
+            RolledToken::Die(dice, Some(modifier)) => {
+                dice.iter().zip(modifier.keeps(dice)).collect()
+            }
+            RolledToken::Die(dice, None) => dice.iter().map(|die| (die, true)).collect(),
+            RolledToken::Constant(_) => vec![],
+        }
+    }
+
+    /// Sum in `i32`, which no number of dice or constants within the limits can overflow.
+    pub fn value(&self) -> i32 {
+        match self {
+            RolledToken::Die(..) => self.kept().iter().map(|die| i32::from(die.value())).sum(),
+            RolledToken::Constant(value) => i32::from(value.get()),
+        }
+    }
+
+    /// Table of the token, a row per die or face counts for pools of more than `pool_threshold`
+    /// dice.
+    pub fn table(&self, locale: Locale, pool_threshold: usize) -> Table {
+        match self {
+            RolledToken::Die(dice, modifier) if dice.len() > pool_threshold => {
+                let mut table = Table::new(modifier_header(modifier, Span::Full, locale));
+                let (kept, dropped): (Vec<_>, Vec<_>) =
+                    self.thrown().into_iter().partition(|(_, kept)| *kept);
+                table.append_rows(&face_counts(&kept));
+                if !dropped.is_empty() {
+                    table.append_row(&TableRow::FullWidth(
+                        format!("// {}", locale.catalog().dropped()),
+                        Alignment::Left,
+                    ));
+                    table.append_rows(&face_counts(&dropped));
+                }
+                table
+            }
+            RolledToken::Die(_, modifier) => {
+                // Over the value and `(dN)` columns, not the `// dropped` one
+                let mut table = Table::new(modifier_header(modifier, Span::Columns(2), locale));
                 table.append_rows(
-                    &dice
-                        .iter()
-                        .map(|d| {
-                            TableRow::Columns(vec![
-                                (d.value().to_string(), Alignment::Right),
-                                (format!("(d{})", d.sides()), Alignment::Left),
-                            ])
+                    &self
+                        .thrown()
+                        .into_iter()
+                        .map(|(d, kept)| {
+                            let mut row = vec![
+                                Cell::new(d.value().to_string(), Alignment::Right)
+                                    .coloured(d.colour().filter(|_| kept)),
+                                Cell::new(format!("(d{})", d.sides()), Alignment::Left),
+                            ];
+                            if !kept {
+                                row.push(Cell::new(
+                                    format!("// {}", locale.catalog().dropped()),
+                                    Alignment::Left,
+                                ));
+                            }
+                            TableRow::Columns(row)
                         })
                         .collect::<Vec<_>>(),
                 );
                 table
             }
-            Token::Constant(value) => Table::new(vec![TableRow::Columns(vec![(
+            RolledToken::Constant(value) => Table::new(vec![TableRow::columns(vec![(
                 value.to_string(),
                 Alignment::Right,
             )])]),
@@ -146,25 +382,401 @@ impl Into<Table> for &Token {
     }
 }
 
-pub struct Tokens(Vec<Token>);
+/// Row with the modifier of some dice, spanning `span` of the columns of the rows below it.
+fn modifier_header(modifier: &Option<Modifier>, span: Span, locale: Locale) -> Vec<TableRow> {
+    modifier
+        .iter()
+        .map(|modifier| {
+            TableRow::columns(vec![Cell::new(
+                format!("// {}", modifier.localize(locale)),
+                Alignment::Center,
+            )
+            .spanning(span)])
+        })
+        .collect()
+}
+
+/// Rows of cells like `3×7` for every face that came up, the kept ones coloured.
+fn face_counts(dice: &[(&Die, bool)]) -> Vec<TableRow> {
+    dice.iter()
+        .map(|(die, kept)| ((die.value(), *kept), *die))
+        .into_group_map()
+        .into_iter()
+        .sorted_by_key(|(face, _)| *face)
+        .map(|((face, kept), dice)| {
+            Cell::new(format!("{face}×{}", dice.len()), Alignment::Right)
+                .coloured(dice[0].colour().filter(|_| kept))
+        })
+        .chunks(FACES_PER_ROW)
+        .into_iter()
+        .map(TableRow::columns)
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct RolledTokens(Vec<RolledToken>);
 
-impl Into<Table> for Tokens {
-    fn into(self) -> Table {
-        let mut result = Table::new(vec![]);
+impl From<Vec<RolledToken>> for RolledTokens {
+    fn from(tokens: Vec<RolledToken>) -> Self {
+        Self(tokens)
+    }
+}
+
+impl RolledTokens {
+    /// Whether every token could have been rolled, see [`RolledToken::is_valid`].
+    pub fn is_valid(&self) -> bool {
+        self.0.iter().all(RolledToken::is_valid)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &RolledToken> {
+        self.0.iter()
+    }
+
+    /// Critical when a kept d20 shows 20 and none shows 1, fumble the other way around.
+    pub fn outcome(&self) -> Option<Outcome> {
+        let d20s = self
+            .0
+            .iter()
+            .flat_map(RolledToken::kept)
+            .filter(|die| u8::from(die.sides()) == 20)
+            .map(|die| die.value())
+            .collect::<Vec<_>>();
+        match (d20s.contains(&20), d20s.contains(&1)) {
+            (true, false) => Some(Outcome::Critical),
+            (false, true) => Some(Outcome::Fumble),
+            _ => None,
+        }
+    }
+
+    pub fn tokens(&self) -> Tokens {
+        Tokens(self.0.iter().map(Token::from).collect())
+    }
+
+    pub fn value(&self) -> i32 {
+        self.0.iter().map(RolledToken::value).sum()
+    }
+
+    /// Every die thrown, kept or not.
+    pub fn dice(&self) -> impl Iterator<Item = &Die> {
+        self.0.iter().flat_map(|token| match token {
+            RolledToken::Die(dice, _) => &dice[..],
+            RolledToken::Constant(_) => &[],
+        })
+    }
+
+    pub fn table(&self, locale: Locale, pool_threshold: usize) -> Table {
+        let mut result = Table::new(vec![
+            TableRow::FullWidth(self.tokens().to_string(), Alignment::Left),
+            TableRow::Separator('#'),
+        ]);
 
         for (i, token) in self.0.iter().enumerate() {
             if i > 0 {
                 result.append_row(&TableRow::Separator('-'));
             }
-            result.append_table(&token.into());
+            result.append_table(&token.table(locale, pool_threshold));
         }
         result.append_row(&TableRow::Separator('='));
 
-        result.append_row(&TableRow::Columns(vec![(
-            self.0.iter().map(Token::value).sum::<i16>().to_string(),
+        result.append_row(&TableRow::columns(vec![(
+            self.value().to_string(),
             Alignment::Right,
         )]));
 
         result
     }
+
+    /// Rows with the min, max and mean of the roll, and where its total lands among all totals.
+    pub fn stats_table(&self, distribution: Option<&Distribution>, locale: Locale) -> Table {
+        let catalog = locale.catalog();
+        let mut result = Table::new(vec![TableRow::Separator('-')]);
+        let Some(distribution) = distribution else {
+            result.append_row(&TableRow::FullWidth(
+                format!("// {}", catalog.stat_unavailable()),
+                Alignment::Left,
+            ));
+            return result;
+        };
+
+        let row = |value: String, label: String| {
+            TableRow::columns(vec![
+                (value, Alignment::Right),
+                (format!("// {label}"), Alignment::Left),
+            ])
+        };
+        let value = self.value();
+        let (below, at_most) = (
+            distribution.cumulative(value - 1),
+            distribution.cumulative(value),
+        );
+        let rank = if 1.0 - below < at_most {
+            catalog.stat_top((1.0 - below) * 100.0)
+        } else {
+            catalog.stat_bottom(at_most * 100.0)
+        };
+        result.append_rows(&[
+            row(distribution.min().to_string(), catalog.stat_min()),
+            row(distribution.max().to_string(), catalog.stat_max()),
+            row(format!("{:.2}", distribution.mean()), catalog.stat_mean()),
+            row(format!("P{:.0}", (below + at_most) / 2.0 * 100.0), rank),
+        ]);
+
+        result
+    }
+}
+
+/// Assert that two floats only differ by rounding errors.
+#[cfg(test)]
+pub fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+}
+
+#[cfg(test)]
+mod test {
+    use indoc::indoc;
+    use itertools::Itertools;
+    use rand::SeedableRng;
+
+    use super::{rng::SeededRng, *};
+
+    fn die(sides: u8, value: i16) -> Die {
+        Die::new(NonZeroU8::new(sides).unwrap(), value)
+    }
+
+    #[test]
+    fn roll_is_deterministic_for_seed() {
+        let tokens = "4d6:adv3 + d20 - 2".parse::<Tokens>().unwrap();
+        assert_eq!(
+            tokens.roll(&mut SeededRng::seed_from_u64(42)),
+            tokens.roll(&mut SeededRng::seed_from_u64(42))
+        );
+        assert_ne!(
+            tokens.roll(&mut SeededRng::seed_from_u64(42)),
+            tokens.roll(&mut SeededRng::seed_from_u64(43))
+        );
+    }
+
+    #[test]
+    fn roll_stays_in_range() {
+        let mut rng = SeededRng::seed_from_u64(0);
+        for sides in [1, 2, 6, 20, 255] {
+            let dice = Dice::new(NonZeroU8::new(255).unwrap(), NonZeroU8::new(sides).unwrap());
+            for die in dice.roll(&mut rng) {
+                assert_eq!(die.sides(), dice.sides());
+                assert!((1..=sides as i16).contains(&die.value()), "{die:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn thrown_keeps_roll_order() {
+        let dice = vec![die(6, 3), die(6, 6), die(6, 1), die(6, 3)];
+        let thrown = |modifier| {
+            RolledToken::Die(dice.clone(), Some(modifier))
+                .thrown()
+                .into_iter()
+                .map(|(die, kept)| (die.value(), kept))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            thrown(Modifier::Advantage(NonZeroU8::new(3).unwrap())),
+            vec![(3, true), (6, true), (1, false), (3, true)]
+        );
+        assert_eq!(
+            thrown(Modifier::Disadvantage(NonZeroU8::new(1).unwrap())),
+            vec![(3, false), (6, false), (1, true), (3, false)]
+        );
+    }
+
+    #[test]
+    fn table_marks_dropped_dice() {
+        let rolled = RolledTokens(vec![RolledToken::Die(
+            vec![die(20, 4), die(20, 17)],
+            Some(Modifier::Advantage(NonZeroU8::new(1).unwrap())),
+        )]);
+        assert_eq!(
+            rolled
+                .table(Locale::English, 10)
+                .to_string()
+                .lines()
+                .map(str::trim_end)
+                .join("\n"),
+            indoc! {"
+                2d20:adv1
+                ################################
+                // Advantage (take 1)
+                 4 (d20)              // dropped
+                17 (d20)
+                ================================
+                17"}
+        );
+    }
+
+    #[test]
+    fn is_valid_works() {
+        let mut rng = SeededRng::seed_from_u64(0);
+        let rolled = "4d6:adv3 + d20 - 2"
+            .parse::<Tokens>()
+            .unwrap()
+            .roll(&mut rng);
+        assert!(rolled.is_valid());
+
+        let advantage = |take| Some(Modifier::Advantage(NonZeroU8::new(take).unwrap()));
+        for token in [
+            RolledToken::Die(vec![], None),
+            RolledToken::Die(vec![die(6, 0)], None),
+            RolledToken::Die(vec![die(6, -1)], None),
+            RolledToken::Die(vec![die(6, 7)], None),
+            RolledToken::Die(vec![die(6, 1), die(8, 1)], None),
+            RolledToken::Die(vec![die(6, 1)], advantage(2)),
+            RolledToken::Die(vec![die(6, 1); 256], None),
+        ] {
+            assert!(!token.is_valid(), "{token:?}");
+        }
+    }
+
+    #[test]
+    fn die_sides_works() {
+        let sides = |input: &str| input.parse::<Tokens>().unwrap().die_sides().map(u8::from);
+        assert_eq!(sides("d20"), Some(20));
+        assert_eq!(sides("1d6"), Some(6));
+        for input in ["4d6", "d20:adv", "2d20:dis1", "d20 + 1", "5"] {
+            assert_eq!(sides(input), None, "{input}");
+        }
+    }
+
+    #[test]
+    fn table_collapses_pools() {
+        let rolled = RolledTokens(vec![RolledToken::Die(
+            [1, 6, 3, 6, 1, 2, 4, 5, 6, 2]
+                .into_iter()
+                .map(|value| die(6, value))
+                .collect(),
+            Some(Modifier::Advantage(NonZeroU8::new(8).unwrap())),
+        )]);
+        assert_eq!(
+            rolled
+                .table(Locale::English, 4)
+                .to_string()
+                .lines()
+                .map(str::trim_end)
+                .join("\n"),
+            indoc! {"
+                10d6:adv8
+                #####################
+                // Advantage (take 8)
+                2×2 3×1 4×1 5×1   6×3
+                // dropped
+                1×2
+                =====================
+                 34"}
+        );
+        assert_eq!(
+            rolled
+                .table(Locale::English, 10)
+                .to_string()
+                .lines()
+                .count(),
+            15
+        );
+    }
+
+    #[test]
+    fn stats_table_works() {
+        let rolled = RolledTokens(vec![
+            RolledToken::Die(vec![die(6, 6), die(6, 5)], None),
+            RolledToken::Constant(NonZeroI16::new(3).unwrap()),
+        ]);
+        let distribution = rolled.tokens().distribution();
+        assert_eq!(
+            rolled
+                .stats_table(distribution.as_ref(), Locale::English)
+                .to_string()
+                .lines()
+                .map(str::trim_end)
+                .join("\n"),
+            indoc! {"
+                -----------------
+                    5 // Min
+                   15 // Max
+                10.00 // Mean
+                  P94 // Top 8.3%"}
+        );
+
+        let rolled = RolledTokens(vec![RolledToken::Die(vec![die(20, 1)], None)]);
+        assert!(rolled
+            .stats_table(rolled.tokens().distribution().as_ref(), Locale::English)
+            .to_string()
+            .contains("// Bottom 5.0%"));
+        assert!(rolled
+            .stats_table(None, Locale::English)
+            .to_string()
+            .contains("// Too many dice for statistics"));
+    }
+
+    #[test]
+    fn outcome_works() {
+        let d20 = |values: &[i16], modifier| {
+            RolledToken::Die(
+                values.iter().map(|value| die(20, *value)).collect(),
+                modifier,
+            )
+        };
+        let advantage = Some(Modifier::Advantage(NonZeroU8::new(1).unwrap()));
+
+        let outcome = |tokens| RolledTokens(tokens).outcome();
+        assert_eq!(outcome(vec![d20(&[20], None)]), Some(Outcome::Critical));
+        assert_eq!(outcome(vec![d20(&[1], None)]), Some(Outcome::Fumble));
+        assert_eq!(
+            outcome(vec![d20(&[1, 20], advantage.clone())]),
+            Some(Outcome::Critical)
+        );
+        assert_eq!(outcome(vec![d20(&[1, 12], advantage)]), None);
+        assert_eq!(outcome(vec![d20(&[1], None), d20(&[20], None)]), None);
+        assert_eq!(outcome(vec![RolledToken::Die(vec![die(6, 6)], None)]), None);
+    }
+
+    #[test]
+    fn rolled_value_works() {
+        let rolled = RolledTokens(vec![
+            RolledToken::Die(
+                vec![die(6, 2), die(6, 5), die(6, 1), die(6, 4)],
+                Some(Modifier::Advantage(NonZeroU8::new(3).unwrap())),
+            ),
+            RolledToken::Die(
+                vec![die(20, 17), die(20, 3)],
+                Some(Modifier::Disadvantage(NonZeroU8::new(1).unwrap())),
+            ),
+            RolledToken::Constant(NonZeroI16::new(-2).unwrap()),
+        ]);
+        assert_eq!(rolled.value(), 11 + 3 - 2);
+        assert_eq!(
+            rolled.tokens(),
+            "4d6:adv3 + 2d20:dis - 2".parse::<Tokens>().unwrap()
+        );
+    }
+
+    #[test]
+    fn rolled_value_does_not_overflow() {
+        let rng = &mut SeededRng::seed_from_u64(0);
+        assert_eq!(
+            "32767 + 1".parse::<Tokens>().unwrap().roll(rng).value(),
+            32768
+        );
+        assert_eq!(
+            "-32768 - 32768"
+                .parse::<Tokens>()
+                .unwrap()
+                .roll(rng)
+                .value(),
+            -65536
+        );
+
+        let rolled = RolledTokens(vec![
+            RolledToken::Die(vec![die(255, 255); 255], None),
+            RolledToken::Die(vec![die(255, 255); 255], None),
+        ]);
+        assert_eq!(rolled.value(), 2 * 255 * 255);
+    }
 }
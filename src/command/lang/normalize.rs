@@ -0,0 +1,97 @@
+use itertools::Itertools;
+use regex::Regex;
+
+/// Rewrite loosely typed input into the space separated form the tokens are parsed from.
+///
+/// Mobile keyboards like to capitalize, insert spaces and substitute look-alike characters,
+/// so `2 D6 −1` becomes `2d6 - 1` before parsing.
+pub fn normalize(s: &str) -> String {
+    let s = s
+        .chars()
+        .map(|c| match c {
+            '\u{2212}' | '\u{2010}' | '\u{2011}' | '\u{2012}' | '\u{2013}' | '\u{2014}'
+            | '\u{fe63}' | '\u{ff0d}' => '-',
+            '\u{fe62}' | '\u{ff0b}' => '+',
+            '\u{ff1a}' => ':',
+            '\u{ff10}'..='\u{ff19}' => char::from_u32(c as u32 - 0xff10 + '0' as u32)
+                .expect("Fullwidth digits map to ASCII digits"),
+            c => c,
+        })
+        .collect::<String>()
+        .to_lowercase();
+
+    // `d20+5` -> `d20 + 5`
+    let s = Regex::new(r"([+-])").unwrap().replace_all(&s, " $1 ");
+    // `2 d6` -> `2d6`, only at the start of a term since `d20 5 d6` is ambiguous
+    let s = Regex::new(r"(^\s*|[+-]\s*)(\d+)\s+d")
+        .unwrap()
+        .replace_all(&s, "${1}${2}d");
+    // `d 6` -> `d6`
+    let s = Regex::new(r"d\s+(\d)").unwrap().replace_all(&s, "d$1");
+    // `2d20 : adv` -> `2d20:adv`
+    let s = Regex::new(r"\s*:\s*").unwrap().replace_all(&s, ":");
+
+    s.split_whitespace().join(" ")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn normalize_uppercase_works() {
+        assert_eq!(normalize("2D6"), "2d6");
+        assert_eq!(normalize("4D6:ADV3"), "4d6:adv3");
+    }
+
+    #[test]
+    fn normalize_spaced_count_works() {
+        assert_eq!(normalize("2 d6"), "2d6");
+        assert_eq!(normalize("1 + 2  d6"), "1 + 2d6");
+        assert_eq!(normalize("5 d20"), "5d20");
+    }
+
+    #[test]
+    fn normalize_spaced_count_needs_term_start() {
+        assert_eq!(normalize("d20 5 d6"), "d20 5 d6");
+        assert_eq!(normalize("2d6 - d4"), "2d6 - d4");
+        assert_eq!(normalize("2d6 -d4"), "2d6 - d4");
+    }
+
+    #[test]
+    fn normalize_spaced_sides_works() {
+        assert_eq!(normalize("d 20"), "d20");
+        assert_eq!(normalize("3 d 4"), "3d4");
+    }
+
+    #[test]
+    fn normalize_spaced_modifier_works() {
+        assert_eq!(normalize("2d20 : adv"), "2d20:adv");
+        assert_eq!(normalize("4d6: dis3"), "4d6:dis3");
+    }
+
+    #[test]
+    fn normalize_joined_signs_works() {
+        assert_eq!(normalize("d20+5"), "d20 + 5");
+        assert_eq!(normalize("d20 +5"), "d20 + 5");
+        assert_eq!(normalize("2d6-1+d4"), "2d6 - 1 + d4");
+    }
+
+    #[test]
+    fn normalize_unicode_signs_works() {
+        assert_eq!(normalize("d20\u{2212}2"), "d20 - 2");
+        assert_eq!(normalize("d20 \u{2013} 2"), "d20 - 2");
+        assert_eq!(normalize("d20\u{ff0b}\u{ff13}"), "d20 + 3");
+    }
+
+    #[test]
+    fn normalize_unicode_whitespace_works() {
+        assert_eq!(normalize("\u{a0}2d6\u{3000}+\t3\n"), "2d6 + 3");
+    }
+
+    #[test]
+    fn normalize_separate_dice_unchanged() {
+        assert_eq!(normalize("d20 d6"), "d20 d6");
+        assert_eq!(normalize("2d20:adv 4d6:dis3"), "2d20:adv 4d6:dis3");
+    }
+}
@@ -5,7 +5,7 @@ use std::{
 
 use regex::Regex;
 
-use super::*;
+use super::{normalize::normalize, *};
 
 impl FromStr for Modifier {
     type Err = ParseModifierError;
@@ -156,10 +156,7 @@ impl FromStr for Token {
                 })
                 .transpose()?;
 
-            Ok(Self::Die(
-                (0..u8::from(count)).map(|_| Die::new(sides)).collect(),
-                modifier,
-            ))
+            Ok(Self::Die(Dice::new(count, sides), modifier))
         } else {
             Err(Self::Err::new(s.to_string(), TokenErrorKind::Invalid))
         }
@@ -170,10 +167,50 @@ impl FromStr for Tokens {
     type Err = ParseTokenError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let tokens = s
-            .split_whitespace()
-            .map(|token| token.parse::<Token>())
-            .collect::<Result<Vec<_>, _>>()?;
+        let s = normalize(s);
+        let mut tokens = vec![];
+        let mut sign = None;
+
+        for word in s.split_whitespace() {
+            match word {
+                "+" | "-" => {
+                    if sign.replace(word).is_some() {
+                        return Err(Self::Err::new(word.to_string(), TokenErrorKind::Invalid));
+                    }
+                }
+                word => {
+                    let sign = sign.take();
+                    let token = match sign {
+                        Some("-") => format!("-{word}"),
+                        _ => word.to_string(),
+                    };
+                    let token = match token.parse::<Token>() {
+                        Err(_) if sign == Some("-") && word.parse::<Token>().is_ok() => {
+                            return Err(Self::Err::new(token, TokenErrorKind::Subtracted));
+                        }
+                        token => token?,
+                    };
+
+                    // `d20 5 d6` could be `d20 + 5d6` as well as `d20 + 5 + d6`
+                    if let (None, Some(Token::Constant(constant)), Token::Die(..)) =
+                        (sign, tokens.last(), &token)
+                    {
+                        if word.starts_with('d') {
+                            return Err(Self::Err::new(
+                                format!("{} {word}", constant.unsigned_abs()),
+                                TokenErrorKind::Ambiguous,
+                            ));
+                        }
+                    }
+                    tokens.push(token);
+                }
+            }
+        }
+
+        if let Some(sign) = sign {
+            return Err(Self::Err::new(sign.to_string(), TokenErrorKind::Invalid));
+        }
+
         Ok(Self(tokens))
     }
 }
@@ -182,6 +219,8 @@ impl FromStr for Tokens {
 mod test {
     use std::num::{IntErrorKind, ParseIntError};
 
+    use itertools::Itertools;
+
     fn parse_int_error(kind: IntErrorKind) -> ParseIntError {
         match kind {
             IntErrorKind::Empty => "".parse::<u8>().unwrap_err(),
@@ -226,7 +265,10 @@ mod test {
     fn parse_d2_works() {
         assert_eq!(
             "d2".parse(),
-            Ok(Token::Die(vec![Die::new(NonZeroU8::new(2).unwrap())], None))
+            Ok(Token::Die(
+                Dice::new(NonZeroU8::new(1).unwrap(), NonZeroU8::new(2).unwrap()),
+                None
+            ))
         );
     }
 
@@ -249,11 +291,7 @@ mod test {
         assert_eq!(
             "3d4".parse(),
             Ok(Token::Die(
-                vec![
-                    Die::new(NonZeroU8::new(4).unwrap()),
-                    Die::new(NonZeroU8::new(4).unwrap()),
-                    Die::new(NonZeroU8::new(4).unwrap())
-                ],
+                Dice::new(NonZeroU8::new(3).unwrap(), NonZeroU8::new(4).unwrap()),
                 None
             ))
         );
@@ -278,10 +316,7 @@ mod test {
         assert_eq!(
             "2d20:adv".parse(),
             Ok(Token::Die(
-                vec![
-                    Die::new(NonZeroU8::new(20).unwrap()),
-                    Die::new(NonZeroU8::new(20).unwrap()),
-                ],
+                Dice::new(NonZeroU8::new(2).unwrap(), NonZeroU8::new(20).unwrap()),
                 Some(Modifier::Advantage(NonZeroU8::new(1).unwrap()))
             ))
         );
@@ -292,12 +327,7 @@ mod test {
         assert_eq!(
             "4d6:adv3".parse(),
             Ok(Token::Die(
-                vec![
-                    Die::new(NonZeroU8::new(6).unwrap()),
-                    Die::new(NonZeroU8::new(6).unwrap()),
-                    Die::new(NonZeroU8::new(6).unwrap()),
-                    Die::new(NonZeroU8::new(6).unwrap()),
-                ],
+                Dice::new(NonZeroU8::new(4).unwrap(), NonZeroU8::new(6).unwrap()),
                 Some(Modifier::Advantage(NonZeroU8::new(3).unwrap()))
             ))
         );
@@ -350,10 +380,7 @@ mod test {
         assert_eq!(
             "2d20:dis".parse(),
             Ok(Token::Die(
-                vec![
-                    Die::new(NonZeroU8::new(20).unwrap()),
-                    Die::new(NonZeroU8::new(20).unwrap()),
-                ],
+                Dice::new(NonZeroU8::new(2).unwrap(), NonZeroU8::new(20).unwrap()),
                 Some(Modifier::Disadvantage(NonZeroU8::new(1).unwrap()))
             ))
         );
@@ -364,12 +391,7 @@ mod test {
         assert_eq!(
             "4d6:dis3".parse(),
             Ok(Token::Die(
-                vec![
-                    Die::new(NonZeroU8::new(6).unwrap()),
-                    Die::new(NonZeroU8::new(6).unwrap()),
-                    Die::new(NonZeroU8::new(6).unwrap()),
-                    Die::new(NonZeroU8::new(6).unwrap()),
-                ],
+                Dice::new(NonZeroU8::new(4).unwrap(), NonZeroU8::new(6).unwrap()),
                 Some(Modifier::Disadvantage(NonZeroU8::new(3).unwrap()))
             ))
         );
@@ -433,4 +455,115 @@ mod test {
             ))
         );
     }
+
+    #[test]
+    fn parse_signed_tokens_works() {
+        assert_eq!(
+            "2d6 + 3 - 1".parse(),
+            Ok(Tokens(vec![
+                Token::Die(
+                    Dice::new(NonZeroU8::new(2).unwrap(), NonZeroU8::new(6).unwrap()),
+                    None
+                ),
+                Token::Constant(NonZeroI16::new(3).unwrap()),
+                Token::Constant(NonZeroI16::new(-1).unwrap()),
+            ]))
+        );
+    }
+
+    #[test]
+    fn parse_dangling_sign_fails() {
+        assert_eq!(
+            "2d6 +".parse::<Tokens>(),
+            Err(ParseTokenError::new(
+                "+".to_string(),
+                TokenErrorKind::Invalid
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_lenient_tokens_works() {
+        for s in ["2D6 +3", "2 d6+3", "2d6\u{a0}\u{ff0b}\u{ff13}"] {
+            assert_eq!(s.parse::<Tokens>(), "2d6 + 3".parse(), "{s}");
+        }
+        for s in ["d20\u{2212}2", "D20 \u{2013}2", "d 20-2"] {
+            assert_eq!(s.parse::<Tokens>(), "d20 - 2".parse(), "{s}");
+        }
+        assert_eq!("2D20 : ADV".parse::<Tokens>(), "2d20:adv".parse());
+        assert_eq!("5 d20".parse::<Tokens>(), "5d20".parse());
+    }
+
+    #[test]
+    fn parse_subtracted_dice_fails() {
+        for s in ["2d6 - d4", "2d6 -d4", "2d6-d4"] {
+            assert_eq!(
+                s.parse::<Tokens>(),
+                Err(ParseTokenError::new(
+                    "-d4".to_string(),
+                    TokenErrorKind::Subtracted
+                )),
+                "{s}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_ambiguous_count_fails() {
+        assert_eq!(
+            "d20 5 d6".parse::<Tokens>(),
+            Err(ParseTokenError::new(
+                "5 d6".to_string(),
+                TokenErrorKind::Ambiguous
+            ))
+        );
+        assert_eq!("d20 + 5 d6".parse::<Tokens>(), "d20 + 5d6".parse());
+        assert_eq!("d20 5d6".parse::<Tokens>(), "d20 + 5d6".parse());
+    }
+
+    #[test]
+    fn display_normalizes() {
+        assert_eq!("2d6 +3".parse::<Tokens>().unwrap().to_string(), "2d6 + 3");
+        assert_eq!(
+            "d20:adv -2".parse::<Tokens>().unwrap().to_string(),
+            "1d20:adv1 - 2"
+        );
+        assert_eq!(
+            "-1 4d6:dis3".parse::<Tokens>().unwrap().to_string(),
+            "-1 + 4d6:dis3"
+        );
+    }
+
+    #[test]
+    fn display_round_trips() {
+        let mut tokens = vec![];
+        for value in [i16::MIN, -100, -1, 1, 7, i16::MAX] {
+            tokens.push(Token::Constant(NonZeroI16::new(value).unwrap()));
+        }
+        for count in [1, 2, 4, 255] {
+            for sides in [1, 6, 20, 255] {
+                let count = NonZeroU8::new(count).unwrap();
+                let dice = || Dice::new(count, NonZeroU8::new(sides).unwrap());
+                tokens.push(Token::Die(dice(), None));
+                for take in [1, 3, u8::from(count)] {
+                    let Some(take) = NonZeroU8::new(take).filter(|take| *take <= count) else {
+                        continue;
+                    };
+                    tokens.push(Token::Die(dice(), Some(Modifier::Advantage(take))));
+                    tokens.push(Token::Die(dice(), Some(Modifier::Disadvantage(take))));
+                }
+            }
+        }
+
+        for token in &tokens {
+            let printed = token.to_string();
+            assert_eq!(printed.parse::<Token>().as_ref(), Ok(token), "{printed}");
+        }
+
+        for (first, second) in tokens.iter().circular_tuple_windows() {
+            let expression = Tokens(vec![first.clone(), second.clone()]);
+            let printed = expression.to_string();
+            assert_eq!(printed.parse(), Ok(expression), "{printed}");
+        }
+    }
 }
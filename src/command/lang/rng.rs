@@ -0,0 +1,53 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use sha2::{Digest, Sha256};
+
+/// Deterministic source of dice values for live rolls, tests and simulations.
+///
+/// Unlike `rand::rngs::StdRng`, the values produced for a seed are guaranteed to stay the same
+/// between versions of `rand`. Live rolls seed it from [`fair_rng`], so `/verify` can recompute
+/// them.
+pub type SeededRng = ChaCha20Rng;
+
+/// Source of dice values for a provably fair roll.
+///
+/// Values are derived from the secret server seed, the seed chosen by the player and the number of
+/// the roll, so anyone can recompute the roll once the server seed is revealed.
+pub fn fair_rng(server_seed: &str, client_seed: &str, nonce: u64) -> SeededRng {
+    SeededRng::from_seed(Sha256::digest(format!("{server_seed}:{client_seed}:{nonce}")).into())
+}
+
+/// Hash of a server seed, published before the seed is used.
+pub fn commitment(server_seed: &str) -> String {
+    Sha256::digest(server_seed)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use rand::Rng;
+
+    use super::*;
+
+    #[test]
+    fn fair_rng_is_deterministic() {
+        let values = |server: &str, client: &str, nonce: u64| {
+            let mut rng = fair_rng(server, client, nonce);
+            (0..8).map(|_| rng.gen::<u32>()).collect::<Vec<_>>()
+        };
+        assert_eq!(values("server", "client", 0), values("server", "client", 0));
+        assert_ne!(values("server", "client", 0), values("server", "client", 1));
+        assert_ne!(values("server", "client", 0), values("server", "other", 0));
+        assert_ne!(values("server", "client", 0), values("other", "client", 0));
+    }
+
+    #[test]
+    fn commitment_works() {
+        assert_eq!(
+            commitment("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
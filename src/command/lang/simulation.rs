@@ -0,0 +1,162 @@
+use rand::SeedableRng;
+
+use super::{distribution::Distribution, rng::SeededRng, Tokens};
+
+/// z-score of a 95% confidence interval.
+const Z: f64 = 1.96;
+
+/// Value estimated from samples, with its 95% confidence interval.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Estimate<T> {
+    pub value: T,
+    pub low: T,
+    pub high: T,
+}
+
+/// Totals of many rolls of an expression, for when the exact distribution is too expensive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Simulation {
+    /// Sorted.
+    totals: Vec<i32>,
+}
+
+impl Tokens {
+    /// Roll `samples` times with dice from `seed`, the same seed always gives the same result.
+    pub fn simulate(&self, samples: usize, seed: u64) -> Simulation {
+        let mut rng = SeededRng::seed_from_u64(seed);
+        let mut totals = (0..samples.max(1))
+            .map(|_| self.roll(&mut rng).value())
+            .collect::<Vec<_>>();
+        totals.sort_unstable();
+
+        Simulation { totals }
+    }
+}
+
+impl Simulation {
+    pub fn samples(&self) -> usize {
+        self.totals.len()
+    }
+
+    pub fn min(&self) -> i32 {
+        self.totals[0]
+    }
+
+    pub fn max(&self) -> i32 {
+        self.totals[self.totals.len() - 1]
+    }
+
+    pub fn mean(&self) -> Estimate<f64> {
+        let n = self.totals.len() as f64;
+        let mean = self.totals.iter().map(|total| *total as f64).sum::<f64>() / n;
+        let variance = self
+            .totals
+            .iter()
+            .map(|total| (*total as f64 - mean).powi(2))
+            .sum::<f64>()
+            / (n - 1.0).max(1.0);
+        let margin = Z * (variance / n).sqrt();
+
+        Estimate {
+            value: mean,
+            low: mean - margin,
+            high: mean + margin,
+        }
+    }
+
+    /// Total that `p` of the rolls don't exceed, `p` being between 0 and 1.
+    ///
+    /// The interval comes from the normal approximation of the binomial distribution of the rank.
+    pub fn percentile(&self, p: f64) -> Estimate<i32> {
+        let n = self.totals.len() as f64;
+        let last = self.totals.len() - 1;
+        let rank = |rank: f64| (rank.max(0.0) as usize).min(last);
+        let margin = Z * (n * p * (1.0 - p)).sqrt();
+
+        Estimate {
+            value: self.totals[rank((n * p).ceil() - 1.0)],
+            low: self.totals[rank((n * p - margin).floor() - 1.0)],
+            high: self.totals[rank((n * p + margin).ceil() - 1.0)],
+        }
+    }
+
+    /// Share of the rolls whose total matches `predicate`, between 0 and 1.
+    ///
+    /// The interval comes from the normal approximation of the binomial distribution.
+    pub fn share(&self, predicate: impl Fn(i32) -> bool) -> Estimate<f64> {
+        let n = self.totals.len() as f64;
+        let p = self
+            .totals
+            .iter()
+            .filter(|total| predicate(**total))
+            .count() as f64
+            / n;
+        let margin = Z * (p * (1.0 - p) / n).sqrt();
+
+        Estimate {
+            value: p,
+            low: (p - margin).max(0.0),
+            high: (p + margin).min(1.0),
+        }
+    }
+
+    /// Share of the rolls that landed on every total.
+    pub fn distribution(&self) -> Distribution {
+        Distribution::from_samples(&self.totals)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn simulate(s: &str, samples: usize) -> Simulation {
+        s.parse::<Tokens>().unwrap().simulate(samples, 7)
+    }
+
+    #[test]
+    fn simulate_is_deterministic_for_seed() {
+        let tokens = "10d10 + 4d6:adv3".parse::<Tokens>().unwrap();
+        assert_eq!(tokens.simulate(100, 1), tokens.simulate(100, 1));
+        assert_ne!(tokens.simulate(100, 1), tokens.simulate(100, 2));
+    }
+
+    #[test]
+    fn mean_works() {
+        let mean = simulate("2d6 + 1", 20_000).mean();
+        assert!(mean.low < 8.0 && 8.0 < mean.high, "{mean:?}");
+        assert!(mean.high - mean.low < 0.1, "{mean:?}");
+    }
+
+    #[test]
+    fn percentile_works() {
+        let simulation = simulate("2d6", 20_000);
+        assert_eq!((simulation.min(), simulation.max()), (2, 12));
+
+        let median = simulation.percentile(0.5);
+        assert_eq!(median.value, 7);
+        assert!(median.low <= median.value && median.value <= median.high);
+
+        assert_eq!(simulation.percentile(0.0).value, 2);
+        assert_eq!(simulation.percentile(1.0).value, 12);
+    }
+
+    #[test]
+    fn share_works() {
+        let share = simulate("d20", 20_000).share(|total| total >= 15);
+        assert!(share.low < 0.3 && 0.3 < share.high, "{share:?}");
+        assert!(share.high - share.low < 0.02, "{share:?}");
+
+        let never = simulate("d20", 100).share(|total| total > 20);
+        assert_eq!((never.value, never.low, never.high), (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn distribution_matches_exact() {
+        let simulated = simulate("3d6", 50_000).distribution();
+        let exact = "3d6".parse::<Tokens>().unwrap().distribution().unwrap();
+        for (value, p) in exact.iter() {
+            assert!((simulated.probability(value) - p).abs() < 0.01, "{value}");
+        }
+    }
+}
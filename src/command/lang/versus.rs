@@ -0,0 +1,135 @@
+use std::{fmt::Display, str::FromStr};
+
+use regex::Regex;
+
+use super::{
+    diagnostic::{Diagnostic, ErrorCode},
+    error::ParseError,
+    error_token::ParseTokenError,
+    Tokens,
+};
+use crate::command::locale::{Locale, Localize};
+
+/// Two rolls to compare, like `2d6 + 3 vs 1d12 + 4`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Versus {
+    left: Tokens,
+    right: Tokens,
+}
+
+impl Versus {
+    pub fn left(&self) -> &Tokens {
+        &self.left
+    }
+
+    pub fn right(&self) -> &Tokens {
+        &self.right
+    }
+}
+
+impl Display for Versus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} vs {}", self.left, self.right)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum VersusErrorKind {
+    Separator,
+    Tokens(ParseTokenError),
+}
+
+impl Localize for VersusErrorKind {
+    fn localize(&self, locale: Locale) -> String {
+        let catalog = locale.catalog();
+        match self {
+            VersusErrorKind::Separator => catalog.versus_separator(),
+            VersusErrorKind::Tokens(e) => catalog.versus_tokens(&e.localize(locale)),
+        }
+    }
+}
+
+impl Display for VersusErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.localize(Locale::English))
+    }
+}
+
+impl Diagnostic for VersusErrorKind {
+    fn code(&self) -> ErrorCode {
+        match self {
+            VersusErrorKind::Separator => ErrorCode::new(600, "versus-separator-missing"),
+            VersusErrorKind::Tokens(_) => ErrorCode::new(601, "versus-tokens-invalid"),
+        }
+    }
+
+    fn cause(&self) -> Option<&dyn Diagnostic> {
+        match self {
+            VersusErrorKind::Separator => None,
+            VersusErrorKind::Tokens(e) => Some(e),
+        }
+    }
+}
+
+pub type ParseVersusError = ParseError<VersusErrorKind>;
+
+impl FromStr for Versus {
+    type Err = ParseVersusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let sides = Regex::new(r"(?i)\s+vs\.?\s+")
+            .unwrap()
+            .split(s)
+            .collect::<Vec<_>>();
+        let [left, right] = sides[..] else {
+            return Err(Self::Err::new(s.to_string(), VersusErrorKind::Separator));
+        };
+
+        let tokens = [left, right]
+            .into_iter()
+            .map(str::parse::<Tokens>)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| Self::Err::new(s.to_string(), VersusErrorKind::Tokens(e)))?;
+        let [left, right] = <[Tokens; 2]>::try_from(tokens).expect("There are always two sides");
+
+        Ok(Self { left, right })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_versus_works() {
+        let versus = "2d6+3 vs 1d12+4".parse::<Versus>().unwrap();
+        assert_eq!(versus.left(), &"2d6 + 3".parse::<Tokens>().unwrap());
+        assert_eq!(versus.right(), &"1d12 + 4".parse::<Tokens>().unwrap());
+        assert_eq!(versus.to_string(), "2d6 + 3 vs 1d12 + 4");
+
+        assert_eq!(
+            "2d20:adv VS. d20 + 5"
+                .parse::<Versus>()
+                .unwrap()
+                .to_string(),
+            "2d20:adv1 vs 1d20 + 5"
+        );
+    }
+
+    #[test]
+    fn parse_versus_fails() {
+        for s in ["2d6 + 3", "d6 vs d8 vs d10", "2d6vs1d12"] {
+            assert_eq!(
+                s.parse::<Versus>(),
+                Err(ParseVersusError::new(
+                    s.to_string(),
+                    VersusErrorKind::Separator
+                ))
+            );
+        }
+        assert_eq!(
+            "d6 vs d0".parse::<Versus>().unwrap_err().code(),
+            ErrorCode::new(601, "versus-tokens-invalid")
+        );
+    }
+}
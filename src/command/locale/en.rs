@@ -0,0 +1,342 @@
+use indoc::indoc;
+
+use super::Catalog;
+
+pub struct English;
+
+impl Catalog for English {
+    fn roll_help(&self) -> &'static str {
+        indoc! {"
+            Valid tokens are:
+            - `1` - constant, bonus
+            - `d20` - 20-sided die
+            - `4d6` - 4 6-sided dice
+            - `2d20:adv` - 2 20-sided dice, pick highest
+            - `4d6:dis3` - 4 6-sided dice, pick 3 lowest
+
+            Tokens are space separated and can be joined with `+` and `-`, e.g. `2d6 + 3`."}
+    }
+
+    fn argument_error(&self, input: &str, reason: &str, usage: &str) -> String {
+        format!("**Cannot parse `{input}` as argument: {reason}**\n{usage}")
+    }
+
+    fn fair_roll(&self, day: u64, commitment: &str, client_seed: &str, nonce: u64) -> String {
+        format!("Server seed of day {day} `{commitment}…`, client seed `{client_seed}`, nonce {nonce}. Check with `/verify` once the seed is revealed.")
+    }
+
+    fn roll_id(&self, id: &str) -> String {
+        format!("Roll `{id}`, show it again with `/replay {id}`.")
+    }
+
+    fn replay_header(&self, id: &str, user: u64, channel: u64, time: u64) -> String {
+        format!("Roll `{id}` by <@{user}> in <#{channel}> on <t:{time}:f>")
+    }
+
+    fn replay_not_found(&self, id: &str) -> String {
+        format!("No roll has the ID `{id}`")
+    }
+
+    fn verify_commitment(&self, commitment: &str) -> String {
+        format!("-# The hash of this server seed is `{commitment}`, it has to match the one published for the day of the roll.")
+    }
+
+    fn settings_output(&self, output: &str) -> String {
+        format!("Output: {output}")
+    }
+
+    fn settings_locale(&self, locale: Option<&str>) -> String {
+        match locale {
+            Some(locale) => format!("Language: {locale}"),
+            None => "Language: the one of each member's Discord client".to_string(),
+        }
+    }
+
+    fn stats(&self) -> &'static str {
+        "Statistics"
+    }
+
+    fn more_terms(&self, count: usize) -> String {
+        format!("And {count} more terms")
+    }
+
+    fn settings_pool_threshold(&self, threshold: u16) -> String {
+        format!("Dice pools larger than {threshold} are shown as face counts")
+    }
+
+    fn settings_style(&self, style: &str) -> String {
+        format!("Table style: {style}")
+    }
+
+    fn seed_commitment(&self, day: u64, commitment: &str) -> String {
+        format!("Hash of the server seed of today (day {day}): `{commitment}`")
+    }
+
+    fn seed_revealed(&self, day: u64, seed: &str) -> String {
+        format!("Server seed of day {day}: `{seed}`")
+    }
+
+    fn seed_not_revealed(&self, day: u64) -> String {
+        format!("Server seed of day {day} is not revealed")
+    }
+
+    fn seed_none_revealed(&self) -> String {
+        "No server seed is revealed yet".to_string()
+    }
+
+    fn seed_client(&self, client_seed: &str, nonce: u64) -> String {
+        format!("Your client seed: `{client_seed}`, next nonce: {nonce}")
+    }
+
+    fn seed_client_invalid(&self, max: usize) -> String {
+        format!("Client seeds are up to {max} letters, digits, `_` or `-`")
+    }
+
+    fn odds_simulated(&self, samples: usize, seed: u64) -> String {
+        format!("-# Estimated from {samples} rolls with seed {seed}, ranges are 95% confidence intervals.")
+    }
+
+    fn stat_percentile(&self, percentile: u8) -> String {
+        format!("P{percentile}")
+    }
+
+    fn stat_min(&self) -> String {
+        "Min".to_string()
+    }
+
+    fn stat_max(&self) -> String {
+        "Max".to_string()
+    }
+
+    fn stat_mean(&self) -> String {
+        "Mean".to_string()
+    }
+
+    fn stat_median(&self) -> String {
+        "Median".to_string()
+    }
+
+    fn stat_std_dev(&self) -> String {
+        "Std dev".to_string()
+    }
+
+    fn stat_top(&self, share: f64) -> String {
+        format!("Top {share:.1}%")
+    }
+
+    fn stat_bottom(&self, share: f64) -> String {
+        format!("Bottom {share:.1}%")
+    }
+
+    fn stat_unavailable(&self) -> String {
+        "Too many dice for statistics".to_string()
+    }
+
+    fn chance(&self, check: &str, chance: f64) -> String {
+        format!("**{chance:.2}%** chance of `{check}`")
+    }
+
+    fn chance_estimated(&self, check: &str, chance: f64, low: f64, high: f64) -> String {
+        format!("**{chance:.1}%** ({low:.1}..{high:.1}%) chance of `{check}`")
+    }
+
+    fn chance_naturals(&self, twenty: f64, one: f64, chance: f64) -> String {
+        format!("natural 20: {twenty:.2}%, natural 1: {one:.2}%, **{chance:.2}%** if a natural 20 always hits and a natural 1 always misses")
+    }
+
+    fn compare_total(&self) -> String {
+        "Total".to_string()
+    }
+
+    fn compare_wins(&self) -> String {
+        "Wins".to_string()
+    }
+
+    fn compare_ties(&self) -> String {
+        "Ties".to_string()
+    }
+
+    fn compare_mean_difference(&self) -> String {
+        "Mean diff".to_string()
+    }
+
+    fn compare_simulated(&self, samples: usize, seed: u64) -> String {
+        format!("-# Estimated from {samples} rolls of each side with seed {seed}.")
+    }
+
+    fn fairness_header(&self, sides: u8, rolls: u64) -> String {
+        format!("d{sides} - {rolls} rolls")
+    }
+
+    fn fairness_face(&self) -> String {
+        "Face".to_string()
+    }
+
+    fn fairness_count(&self) -> String {
+        "Count".to_string()
+    }
+
+    fn fairness_share(&self) -> String {
+        "Share".to_string()
+    }
+
+    fn fairness_deviation(&self) -> String {
+        "Deviation".to_string()
+    }
+
+    fn fairness_statistic(&self) -> String {
+        "Chi-squared".to_string()
+    }
+
+    fn fairness_degrees_of_freedom(&self) -> String {
+        "Degrees of freedom".to_string()
+    }
+
+    fn fairness_p_value(&self) -> String {
+        "p-value".to_string()
+    }
+
+    fn fairness_fair(&self, p_value: f64) -> String {
+        format!("The faces are consistent with a fair die (p = {p_value:.4}).")
+    }
+
+    fn fairness_unfair(&self, p_value: f64) -> String {
+        format!("**The faces are unlikely to come from a fair die** (p = {p_value:.4}).")
+    }
+
+    fn fairness_too_few(&self, needed: u64) -> String {
+        format!("Not enough rolls for a reliable test yet, at least {needed} are needed.")
+    }
+
+    fn fairness_no_rolls(&self, sides: u8) -> String {
+        format!("No d{sides} was rolled yet")
+    }
+
+    fn fairness_not_die(&self) -> String {
+        "Only a single die without a count or modifier can be tested, like `d20`".to_string()
+    }
+
+    fn dropped(&self) -> &'static str {
+        "dropped"
+    }
+
+    fn advantage(&self, take: u8) -> String {
+        format!("Advantage (take {take})")
+    }
+
+    fn disadvantage(&self, take: u8) -> String {
+        format!("Disadvantage (take {take})")
+    }
+
+    fn parse_error(&self, token: &str, reason: &str) -> String {
+        format!("Error parsing `{token}`.\n{reason}")
+    }
+
+    fn number_empty(&self) -> String {
+        "cannot parse integer from empty string".to_string()
+    }
+
+    fn number_invalid_digit(&self) -> String {
+        "invalid digit found in string".to_string()
+    }
+
+    fn number_too_large(&self) -> String {
+        "number too large to fit in target type".to_string()
+    }
+
+    fn number_too_small(&self) -> String {
+        "number too small to fit in target type".to_string()
+    }
+
+    fn number_zero(&self) -> String {
+        "number would be zero for non-zero type".to_string()
+    }
+
+    fn number_invalid(&self) -> String {
+        "invalid number".to_string()
+    }
+
+    fn modifier_more_than_dice(&self, take: u8, count: u8) -> String {
+        format!("dice to leave ({take}) cannot be more than dice thrown ({count})")
+    }
+
+    fn modifier_invalid(&self) -> String {
+        "Invalid modifier".to_string()
+    }
+
+    fn modifier_advantage(&self, reason: &str) -> String {
+        format!("Advantage count: {reason}")
+    }
+
+    fn modifier_disadvantage(&self, reason: &str) -> String {
+        format!("Disadvantage count: {reason}")
+    }
+
+    fn die_count(&self, reason: &str) -> String {
+        format!("Count: {reason}")
+    }
+
+    fn die_sides(&self, reason: &str) -> String {
+        format!("Sides: {reason}")
+    }
+
+    fn die_modifier(&self, reason: &str) -> String {
+        format!("Modifier: {reason}")
+    }
+
+    fn token_invalid(&self) -> String {
+        "Invalid token".to_string()
+    }
+
+    fn token_die(&self, reason: &str) -> String {
+        format!("Die: {reason}")
+    }
+
+    fn token_constant(&self, reason: &str) -> String {
+        format!("Constant: {reason}")
+    }
+
+    fn token_subtracted(&self) -> String {
+        "Dice cannot be subtracted, only added".to_string()
+    }
+
+    fn token_ambiguous(&self) -> String {
+        "Ambiguous count, write `5d6` or `5 + d6`".to_string()
+    }
+
+    fn check_comparison(&self) -> String {
+        "Missing comparison with a target, like `d20 + 5 >= 15`".to_string()
+    }
+
+    fn check_target(&self, reason: &str) -> String {
+        format!("Target: {reason}")
+    }
+
+    fn check_tokens(&self, reason: &str) -> String {
+        format!("Roll: {reason}")
+    }
+
+    fn versus_separator(&self) -> String {
+        "Missing `vs` between the two rolls, like `2d6 + 3 vs 1d12 + 4`".to_string()
+    }
+
+    fn versus_tokens(&self, reason: &str) -> String {
+        format!("Roll: {reason}")
+    }
+
+    fn limit_dice(&self, count: usize, max: u16) -> String {
+        format!("Too many dice: {count} requested, at most {max} can be rolled at once")
+    }
+
+    fn limit_tokens(&self, count: usize, max: u8) -> String {
+        format!("Too many tokens: {count} requested, at most {max} can be rolled at once")
+    }
+
+    fn output_attached(&self) -> &'static str {
+        "It is attached as a text file instead."
+    }
+
+    fn limit_output_length(&self, length: usize, max: usize) -> String {
+        format!("Result is too long to show: {length} characters, at most {max} fit in a message")
+    }
+}
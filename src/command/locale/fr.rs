@@ -0,0 +1,342 @@
+use indoc::indoc;
+
+use super::Catalog;
+
+pub struct French;
+
+impl Catalog for French {
+    fn roll_help(&self) -> &'static str {
+        indoc! {"
+            Les éléments valides sont :
+            - `1` - constante, bonus
+            - `d20` - dé à 20 faces
+            - `4d6` - 4 dés à 6 faces
+            - `2d20:adv` - 2 dés à 20 faces, garder le plus haut
+            - `4d6:dis3` - 4 dés à 6 faces, garder les 3 plus bas
+
+            Les éléments sont séparés par des espaces et peuvent être reliés par `+` et `-`, par exemple `2d6 + 3`."}
+    }
+
+    fn argument_error(&self, input: &str, reason: &str, usage: &str) -> String {
+        format!("**Impossible de lire l'argument `{input}` : {reason}**\n{usage}")
+    }
+
+    fn fair_roll(&self, day: u64, commitment: &str, client_seed: &str, nonce: u64) -> String {
+        format!("Graine serveur du jour {day} `{commitment}…`, graine client `{client_seed}`, nonce {nonce}. Vérifiable avec `/verifier` une fois la graine révélée.")
+    }
+
+    fn roll_id(&self, id: &str) -> String {
+        format!("Lancer `{id}`, à revoir avec `/revoir {id}`.")
+    }
+
+    fn replay_header(&self, id: &str, user: u64, channel: u64, time: u64) -> String {
+        format!("Lancer `{id}` de <@{user}> dans <#{channel}> le <t:{time}:f>")
+    }
+
+    fn replay_not_found(&self, id: &str) -> String {
+        format!("Aucun lancer n'a l'identifiant `{id}`")
+    }
+
+    fn verify_commitment(&self, commitment: &str) -> String {
+        format!("-# Le hachage de cette graine serveur est `{commitment}`, il doit correspondre à celui publié pour le jour du lancer.")
+    }
+
+    fn settings_output(&self, output: &str) -> String {
+        format!("Affichage : {output}")
+    }
+
+    fn settings_locale(&self, locale: Option<&str>) -> String {
+        match locale {
+            Some(locale) => format!("Langue : {locale}"),
+            None => "Langue : celle du client Discord de chaque membre".to_string(),
+        }
+    }
+
+    fn stats(&self) -> &'static str {
+        "Statistiques"
+    }
+
+    fn more_terms(&self, count: usize) -> String {
+        format!("Et {count} termes de plus")
+    }
+
+    fn settings_pool_threshold(&self, threshold: u16) -> String {
+        format!("Les lancers de plus de {threshold} dés sont résumés par face")
+    }
+
+    fn settings_style(&self, style: &str) -> String {
+        format!("Style des tableaux : {style}")
+    }
+
+    fn seed_commitment(&self, day: u64, commitment: &str) -> String {
+        format!("Hachage de la graine serveur d'aujourd'hui (jour {day}) : `{commitment}`")
+    }
+
+    fn seed_revealed(&self, day: u64, seed: &str) -> String {
+        format!("Graine serveur du jour {day} : `{seed}`")
+    }
+
+    fn seed_not_revealed(&self, day: u64) -> String {
+        format!("La graine serveur du jour {day} n'est pas révélée")
+    }
+
+    fn seed_none_revealed(&self) -> String {
+        "Aucune graine serveur n'est encore révélée".to_string()
+    }
+
+    fn seed_client(&self, client_seed: &str, nonce: u64) -> String {
+        format!("Votre graine client : `{client_seed}`, prochain nonce : {nonce}")
+    }
+
+    fn seed_client_invalid(&self, max: usize) -> String {
+        format!("Les graines client font au plus {max} lettres, chiffres, `_` ou `-`")
+    }
+
+    fn odds_simulated(&self, samples: usize, seed: u64) -> String {
+        format!("-# Estimé à partir de {samples} lancers avec la graine {seed}, les intervalles sont des intervalles de confiance à 95 %.")
+    }
+
+    fn stat_percentile(&self, percentile: u8) -> String {
+        format!("P{percentile}")
+    }
+
+    fn stat_min(&self) -> String {
+        "Min".to_string()
+    }
+
+    fn stat_max(&self) -> String {
+        "Max".to_string()
+    }
+
+    fn stat_mean(&self) -> String {
+        "Moyenne".to_string()
+    }
+
+    fn stat_median(&self) -> String {
+        "Médiane".to_string()
+    }
+
+    fn stat_std_dev(&self) -> String {
+        "Écart type".to_string()
+    }
+
+    fn stat_top(&self, share: f64) -> String {
+        format!("{share:.1} % les plus hauts")
+    }
+
+    fn stat_bottom(&self, share: f64) -> String {
+        format!("{share:.1} % les plus bas")
+    }
+
+    fn stat_unavailable(&self) -> String {
+        "Trop de dés pour les statistiques".to_string()
+    }
+
+    fn chance(&self, check: &str, chance: f64) -> String {
+        format!("**{chance:.2} %** de chances pour `{check}`")
+    }
+
+    fn chance_estimated(&self, check: &str, chance: f64, low: f64, high: f64) -> String {
+        format!("**{chance:.1} %** ({low:.1}..{high:.1} %) de chances pour `{check}`")
+    }
+
+    fn chance_naturals(&self, twenty: f64, one: f64, chance: f64) -> String {
+        format!("20 naturel : {twenty:.2} %, 1 naturel : {one:.2} %, **{chance:.2} %** si un 20 naturel réussit et un 1 naturel échoue toujours")
+    }
+
+    fn compare_total(&self) -> String {
+        "Total".to_string()
+    }
+
+    fn compare_wins(&self) -> String {
+        "Victoires".to_string()
+    }
+
+    fn compare_ties(&self) -> String {
+        "Égalités".to_string()
+    }
+
+    fn compare_mean_difference(&self) -> String {
+        "Écart moyen".to_string()
+    }
+
+    fn compare_simulated(&self, samples: usize, seed: u64) -> String {
+        format!("-# Estimé à partir de {samples} lancers de chaque côté avec la graine {seed}.")
+    }
+
+    fn fairness_header(&self, sides: u8, rolls: u64) -> String {
+        format!("d{sides} - {rolls} lancers")
+    }
+
+    fn fairness_face(&self) -> String {
+        "Face".to_string()
+    }
+
+    fn fairness_count(&self) -> String {
+        "Nombre".to_string()
+    }
+
+    fn fairness_share(&self) -> String {
+        "Part".to_string()
+    }
+
+    fn fairness_deviation(&self) -> String {
+        "Écart".to_string()
+    }
+
+    fn fairness_statistic(&self) -> String {
+        "Khi carré".to_string()
+    }
+
+    fn fairness_degrees_of_freedom(&self) -> String {
+        "Degrés de liberté".to_string()
+    }
+
+    fn fairness_p_value(&self) -> String {
+        "Valeur p".to_string()
+    }
+
+    fn fairness_fair(&self, p_value: f64) -> String {
+        format!("Les faces sont cohérentes avec un dé équilibré (p = {p_value:.4}).")
+    }
+
+    fn fairness_unfair(&self, p_value: f64) -> String {
+        format!("**Les faces ont peu de chances de venir d'un dé équilibré** (p = {p_value:.4}).")
+    }
+
+    fn fairness_too_few(&self, needed: u64) -> String {
+        format!("Pas encore assez de lancers pour un test fiable, il en faut au moins {needed}.")
+    }
+
+    fn fairness_no_rolls(&self, sides: u8) -> String {
+        format!("Aucun d{sides} n'a encore été lancé")
+    }
+
+    fn fairness_not_die(&self) -> String {
+        "Seul un dé sans nombre ni modificateur peut être testé, par exemple `d20`".to_string()
+    }
+
+    fn dropped(&self) -> &'static str {
+        "écarté"
+    }
+
+    fn advantage(&self, take: u8) -> String {
+        format!("Avantage (garder {take})")
+    }
+
+    fn disadvantage(&self, take: u8) -> String {
+        format!("Désavantage (garder {take})")
+    }
+
+    fn parse_error(&self, token: &str, reason: &str) -> String {
+        format!("Erreur de lecture de `{token}`.\n{reason}")
+    }
+
+    fn number_empty(&self) -> String {
+        "impossible de lire un nombre dans une chaîne vide".to_string()
+    }
+
+    fn number_invalid_digit(&self) -> String {
+        "chiffre invalide dans la chaîne".to_string()
+    }
+
+    fn number_too_large(&self) -> String {
+        "nombre trop grand".to_string()
+    }
+
+    fn number_too_small(&self) -> String {
+        "nombre trop petit".to_string()
+    }
+
+    fn number_zero(&self) -> String {
+        "le nombre ne peut pas être zéro".to_string()
+    }
+
+    fn number_invalid(&self) -> String {
+        "nombre invalide".to_string()
+    }
+
+    fn modifier_more_than_dice(&self, take: u8, count: u8) -> String {
+        format!("les dés à garder ({take}) ne peuvent pas dépasser les dés lancés ({count})")
+    }
+
+    fn modifier_invalid(&self) -> String {
+        "Modificateur invalide".to_string()
+    }
+
+    fn modifier_advantage(&self, reason: &str) -> String {
+        format!("Nombre pour l'avantage : {reason}")
+    }
+
+    fn modifier_disadvantage(&self, reason: &str) -> String {
+        format!("Nombre pour le désavantage : {reason}")
+    }
+
+    fn die_count(&self, reason: &str) -> String {
+        format!("Nombre : {reason}")
+    }
+
+    fn die_sides(&self, reason: &str) -> String {
+        format!("Faces : {reason}")
+    }
+
+    fn die_modifier(&self, reason: &str) -> String {
+        format!("Modificateur : {reason}")
+    }
+
+    fn token_invalid(&self) -> String {
+        "Élément invalide".to_string()
+    }
+
+    fn token_die(&self, reason: &str) -> String {
+        format!("Dé : {reason}")
+    }
+
+    fn token_constant(&self, reason: &str) -> String {
+        format!("Constante : {reason}")
+    }
+
+    fn token_subtracted(&self) -> String {
+        "Les dés ne peuvent pas être soustraits, seulement ajoutés".to_string()
+    }
+
+    fn token_ambiguous(&self) -> String {
+        "Nombre ambigu, écrivez `5d6` ou `5 + d6`".to_string()
+    }
+
+    fn check_comparison(&self) -> String {
+        "Il manque une comparaison avec une cible, par exemple `d20 + 5 >= 15`".to_string()
+    }
+
+    fn check_target(&self, reason: &str) -> String {
+        format!("Cible : {reason}")
+    }
+
+    fn check_tokens(&self, reason: &str) -> String {
+        format!("Lancer : {reason}")
+    }
+
+    fn versus_separator(&self) -> String {
+        "Il manque `vs` entre les deux lancers, par exemple `2d6 + 3 vs 1d12 + 4`".to_string()
+    }
+
+    fn versus_tokens(&self, reason: &str) -> String {
+        format!("Lancer : {reason}")
+    }
+
+    fn limit_dice(&self, count: usize, max: u16) -> String {
+        format!("Trop de dés : {count} demandés, au plus {max} peuvent être lancés à la fois")
+    }
+
+    fn limit_tokens(&self, count: usize, max: u8) -> String {
+        format!("Trop d'éléments : {count} demandés, au plus {max} peuvent être lancés à la fois")
+    }
+
+    fn output_attached(&self) -> &'static str {
+        "Il est joint en fichier texte à la place."
+    }
+
+    fn limit_output_length(&self, length: usize, max: usize) -> String {
+        format!("Résultat trop long à afficher : {length} caractères, au plus {max} tiennent dans un message")
+    }
+}
@@ -0,0 +1,223 @@
+mod en;
+mod fr;
+
+use std::num::{IntErrorKind, ParseIntError};
+
+use serde::{Deserialize, Serialize};
+
+use crate::command::Context;
+
+/// Language of the replies.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize, poise::ChoiceParameter,
+)]
+pub enum Locale {
+    #[default]
+    #[name = "English"]
+    English,
+    #[name = "Français"]
+    French,
+}
+
+impl Locale {
+    /// Match a Discord locale like `en-US` or `fr` to a supported one.
+    pub fn from_discord(locale: &str) -> Option<Self> {
+        match locale.split('-').next() {
+            Some("en") => Some(Self::English),
+            Some("fr") => Some(Self::French),
+            _ => None,
+        }
+    }
+
+    /// Discord locale of the language, as used for localized command and choice names.
+    pub fn discord(self) -> &'static str {
+        match self {
+            Locale::English => "en-US",
+            Locale::French => "fr",
+        }
+    }
+
+    /// Locale chosen with `/settings language`, otherwise the one of the invoking user, falling
+    /// back to the preferred locale of the guild.
+    pub fn of(ctx: Context<'_>) -> Self {
+        if let Some(locale) = ctx.data().guild_settings(ctx.guild_id()).locale {
+            return locale;
+        }
+        let guild = match ctx {
+            poise::Context::Application(ctx) => ctx.interaction.guild_locale.as_deref(),
+            poise::Context::Prefix(_) => None,
+        };
+
+        ctx.locale()
+            .into_iter()
+            .chain(guild)
+            .find_map(Self::from_discord)
+            .unwrap_or_default()
+    }
+
+    pub fn catalog(self) -> &'static dyn Catalog {
+        match self {
+            Locale::English => &en::English,
+            Locale::French => &fr::French,
+        }
+    }
+}
+
+pub trait Localize {
+    fn localize(&self, locale: Locale) -> String;
+}
+
+/// Every user-facing message, implemented once per language.
+pub trait Catalog: Sync {
+    fn roll_help(&self) -> &'static str;
+    fn argument_error(&self, input: &str, reason: &str, usage: &str) -> String;
+
+    fn fair_roll(&self, day: u64, commitment: &str, client_seed: &str, nonce: u64) -> String;
+    fn roll_id(&self, id: &str) -> String;
+    fn replay_header(&self, id: &str, user: u64, channel: u64, time: u64) -> String;
+    fn replay_not_found(&self, id: &str) -> String;
+    fn verify_commitment(&self, commitment: &str) -> String;
+    fn settings_output(&self, output: &str) -> String;
+    fn settings_locale(&self, locale: Option<&str>) -> String;
+    fn stats(&self) -> &'static str;
+    fn more_terms(&self, count: usize) -> String;
+    fn settings_pool_threshold(&self, threshold: u16) -> String;
+    fn settings_style(&self, style: &str) -> String;
+    fn seed_commitment(&self, day: u64, commitment: &str) -> String;
+    fn seed_revealed(&self, day: u64, seed: &str) -> String;
+    fn seed_not_revealed(&self, day: u64) -> String;
+    fn seed_none_revealed(&self) -> String;
+    fn seed_client(&self, client_seed: &str, nonce: u64) -> String;
+    fn seed_client_invalid(&self, max: usize) -> String;
+
+    fn odds_simulated(&self, samples: usize, seed: u64) -> String;
+    fn stat_percentile(&self, percentile: u8) -> String;
+    fn stat_min(&self) -> String;
+    fn stat_max(&self) -> String;
+    fn stat_mean(&self) -> String;
+    fn stat_median(&self) -> String;
+    fn stat_std_dev(&self) -> String;
+    fn stat_top(&self, share: f64) -> String;
+    fn stat_bottom(&self, share: f64) -> String;
+    fn stat_unavailable(&self) -> String;
+
+    fn chance(&self, check: &str, chance: f64) -> String;
+    fn chance_estimated(&self, check: &str, chance: f64, low: f64, high: f64) -> String;
+    fn chance_naturals(&self, twenty: f64, one: f64, chance: f64) -> String;
+
+    fn compare_total(&self) -> String;
+    fn compare_wins(&self) -> String;
+    fn compare_ties(&self) -> String;
+    fn compare_mean_difference(&self) -> String;
+    fn compare_simulated(&self, samples: usize, seed: u64) -> String;
+
+    fn fairness_header(&self, sides: u8, rolls: u64) -> String;
+    fn fairness_face(&self) -> String;
+    fn fairness_count(&self) -> String;
+    fn fairness_share(&self) -> String;
+    fn fairness_deviation(&self) -> String;
+    fn fairness_statistic(&self) -> String;
+    fn fairness_degrees_of_freedom(&self) -> String;
+    fn fairness_p_value(&self) -> String;
+    fn fairness_fair(&self, p_value: f64) -> String;
+    fn fairness_unfair(&self, p_value: f64) -> String;
+    fn fairness_too_few(&self, needed: u64) -> String;
+    fn fairness_no_rolls(&self, sides: u8) -> String;
+    fn fairness_not_die(&self) -> String;
+
+    fn dropped(&self) -> &'static str;
+    fn advantage(&self, take: u8) -> String;
+    fn disadvantage(&self, take: u8) -> String;
+
+    fn parse_error(&self, token: &str, reason: &str) -> String;
+    fn number_empty(&self) -> String;
+    fn number_invalid_digit(&self) -> String;
+    fn number_too_large(&self) -> String;
+    fn number_too_small(&self) -> String;
+    fn number_zero(&self) -> String;
+    fn number_invalid(&self) -> String;
+
+    fn modifier_more_than_dice(&self, take: u8, count: u8) -> String;
+    fn modifier_invalid(&self) -> String;
+    fn modifier_advantage(&self, reason: &str) -> String;
+    fn modifier_disadvantage(&self, reason: &str) -> String;
+
+    fn die_count(&self, reason: &str) -> String;
+    fn die_sides(&self, reason: &str) -> String;
+    fn die_modifier(&self, reason: &str) -> String;
+
+    fn token_invalid(&self) -> String;
+    fn token_die(&self, reason: &str) -> String;
+    fn token_constant(&self, reason: &str) -> String;
+    fn token_subtracted(&self) -> String;
+    fn token_ambiguous(&self) -> String;
+
+    fn check_comparison(&self) -> String;
+    fn check_target(&self, reason: &str) -> String;
+    fn check_tokens(&self, reason: &str) -> String;
+
+    fn versus_separator(&self) -> String;
+    fn versus_tokens(&self, reason: &str) -> String;
+
+    fn limit_dice(&self, count: usize, max: u16) -> String;
+    fn limit_tokens(&self, count: usize, max: u8) -> String;
+    fn limit_output_length(&self, length: usize, max: usize) -> String;
+    fn output_attached(&self) -> &'static str;
+}
+
+impl Localize for ParseIntError {
+    fn localize(&self, locale: Locale) -> String {
+        let catalog = locale.catalog();
+        match self.kind() {
+            IntErrorKind::Empty => catalog.number_empty(),
+            IntErrorKind::InvalidDigit => catalog.number_invalid_digit(),
+            IntErrorKind::PosOverflow => catalog.number_too_large(),
+            IntErrorKind::NegOverflow => catalog.number_too_small(),
+            IntErrorKind::Zero => catalog.number_zero(),
+            _ => catalog.number_invalid(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use indoc::indoc;
+
+    use super::*;
+    use crate::command::lang::Tokens;
+
+    #[test]
+    fn from_discord_works() {
+        assert_eq!(Locale::from_discord("en-US"), Some(Locale::English));
+        assert_eq!(Locale::from_discord("en-GB"), Some(Locale::English));
+        assert_eq!(Locale::from_discord("fr"), Some(Locale::French));
+        assert_eq!(Locale::from_discord("de"), None);
+        for locale in [Locale::English, Locale::French] {
+            assert_eq!(Locale::from_discord(locale.discord()), Some(locale));
+        }
+    }
+
+    #[test]
+    fn english_matches_std() {
+        for number in ["", "a", "1000", "-1"] {
+            let e = number.parse::<u8>().unwrap_err();
+            assert_eq!(e.localize(Locale::English), e.to_string());
+        }
+        let e = "0".parse::<std::num::NonZeroU8>().unwrap_err();
+        assert_eq!(e.localize(Locale::English), e.to_string());
+    }
+
+    #[test]
+    fn french_error_works() {
+        let e = "4d6:adv20".parse::<Tokens>().unwrap_err();
+        assert_eq!(
+            e.localize(Locale::French),
+            indoc! {"
+                Erreur de lecture de `4d6:adv20`.
+                Dé : Erreur de lecture de `adv20`.
+                Modificateur : Erreur de lecture de `adv20`.
+                Nombre pour l'avantage : les dés à garder (20) ne peuvent pas dépasser les dés lancés (4)"}
+        );
+        assert_eq!(e.localize(Locale::English), e.to_string());
+    }
+}
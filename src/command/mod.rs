@@ -1,13 +1,156 @@
+mod chance;
+mod compare;
+mod configure;
+mod export;
+mod faces;
+mod fairness;
 mod help;
 mod lang;
+mod locale;
+mod odds;
+mod render;
+mod replay;
 mod roll;
+mod roll_log;
+mod seed;
+mod seeds;
+mod settings;
+mod store;
 mod table;
+mod verify;
 
-pub struct Data;
+use std::sync::Mutex;
+
+use poise::{
+    serenity_prelude::{CreateAttachment, GuildId},
+    CreateReply,
+};
+
+use lang::{Diagnostic, LimitError, ParseCheckError, ParseTokenError, ParseVersusError};
+use locale::{Locale, Localize};
+
+pub struct Data {
+    pub limits: Limits,
+    pub seeds: Mutex<Seeds>,
+    pub faces: Mutex<Faces>,
+    pub log: Mutex<RollLog>,
+    pub settings: Mutex<Settings>,
+}
+
+impl Data {
+    pub fn guild_settings(&self, guild: Option<GuildId>) -> GuildSettings {
+        self.settings
+            .lock()
+            .expect("Settings are not poisoned")
+            .guild(guild)
+    }
+}
 
 pub type Error = Box<dyn std::error::Error + Send + Sync>;
 pub type Context<'a> = poise::Context<'a, Data, Error>;
 pub type Result = std::result::Result<(), Error>;
 
+pub use chance::chance;
+pub use compare::compare;
+pub use configure::configure;
+pub use export::export;
+pub use faces::Faces;
+pub use fairness::fairness;
 pub use help::help;
+pub use lang::Limits;
+pub use odds::odds;
+pub use replay::replay;
 pub use roll::roll;
+pub use roll_log::RollLog;
+pub use seed::seed;
+pub use seeds::Seeds;
+pub use settings::{GuildSettings, Settings};
+pub use verify::verify;
+
+/// Send `reply`, its content uncoloured or attached as a text file when it does not fit in a
+/// message.
+pub async fn send(ctx: Context<'_>, mut reply: CreateReply) -> Result {
+    if let Some(content) = reply.content.take() {
+        reply = match ctx.data().limits.fit_output(&content) {
+            Ok(fitted) => reply.content(fitted.into_owned()),
+            Err(e) => {
+                let locale = Locale::of(ctx);
+                // Text files do not show colours, only their escape codes
+                let attachment = CreateAttachment::bytes(
+                    table::strip_ansi(&content).into_owned().into_bytes(),
+                    format!("{}.txt", ctx.command().name),
+                );
+                reply
+                    .content(format!(
+                        "{}\n{}",
+                        e.localize(locale),
+                        locale.catalog().output_attached()
+                    ))
+                    .attachment(attachment)
+            }
+        };
+    }
+    ctx.send(reply).await?;
+
+    Ok(())
+}
+
+/// Send `content` as a message, or as a text file when it is too long.
+pub async fn say(ctx: Context<'_>, content: String) -> Result {
+    send(ctx, CreateReply::default().content(content)).await
+}
+
+/// Reply to errors of the dice language in the language of the user, leave the rest to poise.
+///
+/// Replies end with the code of the most specific error, the whole chain is logged as JSON.
+pub async fn on_error(error: poise::FrameworkError<'_, Data, Error>) {
+    fn reply<'a, E>(ctx: Context<'a>, e: &E, message: String) -> (Context<'a>, String)
+    where
+        E: Diagnostic + serde::Serialize,
+    {
+        match serde_json::to_string(e) {
+            Ok(json) => tracing::info!("Error in `{}`: {json}", ctx.command().name),
+            Err(e) => tracing::error!("Error while serializing error: {e}"),
+        }
+        (ctx, format!("{message}\n`{}`", e.root_cause().code()))
+    }
+
+    let reply = match &error {
+        poise::FrameworkError::ArgumentParse {
+            ctx,
+            input,
+            error: e,
+            ..
+        } => {
+            let locale = Locale::of(*ctx);
+            let catalog = locale.catalog();
+            let usage = match ctx.command().name.as_str() {
+                "roll" | "chance" | "compare" => catalog.roll_help(),
+                _ => "",
+            };
+            let message = |reason: String| {
+                catalog.argument_error(input.as_deref().unwrap_or_default(), &reason, usage)
+            };
+            if let Some(e) = e.downcast_ref::<ParseTokenError>() {
+                Some(reply(*ctx, e, message(e.localize(locale))))
+            } else if let Some(e) = e.downcast_ref::<ParseCheckError>() {
+                Some(reply(*ctx, e, message(e.localize(locale))))
+            } else {
+                e.downcast_ref::<ParseVersusError>()
+                    .map(|e| reply(*ctx, e, message(e.localize(locale))))
+            }
+        }
+        poise::FrameworkError::Command { ctx, error: e, .. } => e
+            .downcast_ref::<LimitError>()
+            .map(|e| reply(*ctx, e, e.localize(Locale::of(*ctx)))),
+        _ => None,
+    };
+
+    let result = match reply {
+        Some((ctx, reply)) => ctx.say(reply).await.map(|_| ()),
+        None => poise::builtins::on_error(error).await,
+    };
+    if let Err(e) = result {
+        tracing::error!("Error while handling error: {e}");
+    }
+}
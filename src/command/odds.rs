@@ -0,0 +1,143 @@
+use lang::{Distribution, Simulation, Tokens};
+use locale::{Catalog, Locale};
+use table::{Alignment, Style, Table, TableRow};
+
+use crate::command::*;
+
+const HISTOGRAM_ROWS: usize = 20;
+const HISTOGRAM_WIDTH: usize = 20;
+/// Rolls simulated when the exact distribution is too expensive.
+const SAMPLES: usize = 10_000;
+const PERCENTILES: [u8; 5] = [5, 25, 50, 75, 95];
+
+fn row(label: String, value: String) -> TableRow {
+    TableRow::columns(vec![(label, Alignment::Left), (value, Alignment::Right)])
+}
+
+fn header(formula: String) -> Table {
+    Table::new(vec![
+        TableRow::FullWidth(formula, Alignment::Left),
+        TableRow::Separator('#'),
+    ])
+}
+
+fn exact(
+    formula: String,
+    distribution: &Distribution,
+    catalog: &dyn Catalog,
+    style: Style,
+) -> String {
+    let mut table = header(formula);
+    table.append_rows(&[
+        row(catalog.stat_min(), distribution.min().to_string()),
+        row(catalog.stat_max(), distribution.max().to_string()),
+        row(catalog.stat_mean(), format!("{:.2}", distribution.mean())),
+        row(catalog.stat_median(), distribution.median().to_string()),
+        row(
+            catalog.stat_std_dev(),
+            format!("{:.2}", distribution.std_dev()),
+        ),
+    ]);
+    let histogram = Table::histogram(&distribution.buckets(HISTOGRAM_ROWS), HISTOGRAM_WIDTH);
+
+    format!(
+        "\
+```rust
+{}
+{}
+```
+",
+        table.render(style),
+        histogram.render(style)
+    )
+}
+
+fn simulated(
+    formula: String,
+    simulation: &Simulation,
+    seed: u64,
+    catalog: &dyn Catalog,
+    style: Style,
+) -> String {
+    let mut table = header(formula);
+    let mean = simulation.mean();
+    table.append_rows(&[
+        row(catalog.stat_min(), simulation.min().to_string()),
+        row(catalog.stat_max(), simulation.max().to_string()),
+        row(
+            catalog.stat_mean(),
+            format!("{:.2} ({:.2}..{:.2})", mean.value, mean.low, mean.high),
+        ),
+    ]);
+    table.append_rows(
+        &PERCENTILES
+            .iter()
+            .map(|p| {
+                let percentile = simulation.percentile(*p as f64 / 100.0);
+                row(
+                    catalog.stat_percentile(*p),
+                    format!(
+                        "{} ({}..{})",
+                        percentile.value, percentile.low, percentile.high
+                    ),
+                )
+            })
+            .collect::<Vec<_>>(),
+    );
+    let histogram = Table::histogram(
+        &simulation.distribution().buckets(HISTOGRAM_ROWS),
+        HISTOGRAM_WIDTH,
+    );
+
+    format!(
+        "\
+```rust
+{}
+{}
+```
+{}",
+        table.render(style),
+        histogram.render(style),
+        catalog.odds_simulated(simulation.samples(), seed)
+    )
+}
+
+/// Show the probabilities of a roll.
+///
+/// They are exact when possible, otherwise estimated by simulating many rolls.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    name_localized("fr", "probabilites"),
+    description_localized("fr", "Afficher les probabilités d'un lancer.")
+)]
+pub async fn odds(
+    ctx: Context<'_>,
+    #[description = "What to roll, space separated"]
+    #[name_localized("fr", "elements")]
+    #[description_localized("fr", "Ce qu'il faut lancer, séparé par des espaces")]
+    tokens: Tokens,
+) -> Result {
+    let limits = &ctx.data().limits;
+    tokens.check_limits(limits)?;
+
+    let catalog = Locale::of(ctx).catalog();
+    let style = ctx.data().guild_settings(ctx.guild_id()).style;
+    let formula = tokens.to_string();
+    // The exact distribution can take a few seconds too
+    let (tokens, distribution) = tokio::task::spawn_blocking(move || {
+        let distribution = tokens.distribution();
+        (tokens, distribution)
+    })
+    .await?;
+    let reply = match distribution {
+        Some(distribution) => exact(formula, &distribution, catalog, style),
+        None => {
+            let seed = rand::random();
+            let simulation =
+                tokio::task::spawn_blocking(move || tokens.simulate(SAMPLES, seed)).await?;
+            simulated(formula, &simulation, seed, catalog, style)
+        }
+    };
+    say(ctx, reply).await
+}
@@ -0,0 +1,327 @@
+use std::f32::consts::PI;
+
+use tiny_skia::{Color, LineCap, Paint, Path, PathBuilder, Pixmap, Stroke, Transform};
+
+use lang::{Die, RolledToken, RolledTokens};
+use table::Colour;
+
+use crate::command::*;
+
+/// Width and height of the square a die is drawn in.
+const TILE: f32 = 80.0;
+const GAP: f32 = 12.0;
+const MARGIN: f32 = 24.0;
+const TILES_PER_ROW: usize = 10;
+/// Rows of dice drawn at most, so a raised dice limit cannot make huge images.
+const MAX_ROWS: usize = 12;
+/// Height of the signs and constants between dice.
+const TEXT_HEIGHT: f32 = 28.0;
+const TOTAL_HEIGHT: f32 = 56.0;
+
+const BACKGROUND: (u8, u8, u8) = (0x2b, 0x2d, 0x31);
+const NEUTRAL: (u8, u8, u8) = (0x58, 0x65, 0xf2);
+const GREEN: (u8, u8, u8) = (0x2e, 0xcc, 0x71);
+const RED: (u8, u8, u8) = (0xe7, 0x4c, 0x3c);
+const DROPPED: (u8, u8, u8) = (0x4f, 0x54, 0x5c);
+const INK: (u8, u8, u8) = (0xff, 0xff, 0xff);
+
+enum Item<'a> {
+    Die(&'a Die, bool),
+    Text(String),
+}
+
+impl Item<'_> {
+    fn width(&self) -> f32 {
+        match self {
+            Item::Die(..) => TILE,
+            Item::Text(text) => text_width(text, TEXT_HEIGHT),
+        }
+    }
+}
+
+fn paint((r, g, b): (u8, u8, u8)) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color(Color::from_rgba8(r, g, b, 0xff));
+    paint.anti_alias = true;
+    paint
+}
+
+/// Dice in the order they were rolled, with the signs and constants between them.
+fn items(rolled: &RolledTokens) -> Vec<Item<'_>> {
+    let mut items = vec![];
+    for (i, token) in rolled.iter().enumerate() {
+        match token {
+            RolledToken::Die(..) => {
+                if i > 0 {
+                    items.push(Item::Text("+".to_string()));
+                }
+                items.extend(
+                    token
+                        .thrown()
+                        .into_iter()
+                        .map(|(die, kept)| Item::Die(die, kept)),
+                );
+            }
+            RolledToken::Constant(value) if i > 0 => items.push(Item::Text(format!(
+                "{}{}",
+                if value.is_negative() { '-' } else { '+' },
+                value.unsigned_abs()
+            ))),
+            RolledToken::Constant(value) => items.push(Item::Text(value.to_string())),
+        }
+    }
+    items
+}
+
+/// Dice and the total drawn as a PNG, each die in the shape of its kind, or nothing when they
+/// take more than [`MAX_ROWS`] rows.
+pub fn png(rolled: &RolledTokens) -> Option<Vec<u8>> {
+    let max_width = TILES_PER_ROW as f32 * (TILE + GAP) - GAP;
+    let mut rows: Vec<Vec<Item>> = vec![vec![]];
+    for item in items(rolled) {
+        let row = rows.last_mut().expect("There is always a row");
+        if !row.is_empty() && row_width(row) + GAP + item.width() > max_width {
+            rows.push(vec![item]);
+        } else {
+            row.push(item);
+        }
+    }
+    if rows.len() > MAX_ROWS {
+        return None;
+    }
+
+    let total = format!("={}", rolled.value());
+    let width = rows
+        .iter()
+        .map(|row| row_width(row))
+        .fold(text_width(&total, TOTAL_HEIGHT), f32::max)
+        + 2.0 * MARGIN;
+    let height = 2.0 * MARGIN + rows.len() as f32 * (TILE + GAP) + GAP + TOTAL_HEIGHT;
+    let mut pixmap =
+        Pixmap::new(width.ceil() as u32, height.ceil() as u32).expect("The image is not empty");
+    let (r, g, b) = BACKGROUND;
+    pixmap.fill(Color::from_rgba8(r, g, b, 0xff));
+
+    for (i, row) in rows.iter().enumerate() {
+        let top = MARGIN + i as f32 * (TILE + GAP);
+        let mut left = MARGIN;
+        for item in row {
+            match item {
+                Item::Die(die, kept) => draw_die(&mut pixmap, die, *kept, left, top),
+                Item::Text(text) => draw_text(
+                    &mut pixmap,
+                    text,
+                    left,
+                    top + (TILE - TEXT_HEIGHT) / 2.0,
+                    TEXT_HEIGHT,
+                ),
+            }
+            left += item.width() + GAP;
+        }
+    }
+    draw_text(
+        &mut pixmap,
+        &total,
+        (width - text_width(&total, TOTAL_HEIGHT)) / 2.0,
+        height - MARGIN - TOTAL_HEIGHT,
+        TOTAL_HEIGHT,
+    );
+
+    Some(
+        pixmap
+            .encode_png()
+            .expect("Encoding to memory does not fail"),
+    )
+}
+
+fn row_width(row: &[Item]) -> f32 {
+    row.iter().map(|item| item.width() + GAP).sum::<f32>() - GAP
+}
+
+/// Regular polygon with `corners` corners on the circle of radius `radius`, the first at
+/// `start` radians.
+fn polygon(cx: f32, cy: f32, radius: f32, corners: usize, start: f32) -> Path {
+    let mut path = PathBuilder::new();
+    for i in 0..corners {
+        let angle = start + 2.0 * PI * i as f32 / corners as f32;
+        let (x, y) = (cx + radius * angle.cos(), cy + radius * angle.sin());
+        if i == 0 {
+            path.move_to(x, y);
+        } else {
+            path.line_to(x, y);
+        }
+    }
+    path.close();
+    path.finish().expect("Polygons have corners")
+}
+
+/// Outline of a die of the given kind, the ones without a usual shape drawn as circles.
+fn shape(sides: u8, cx: f32, cy: f32, radius: f32) -> Path {
+    let up = -PI / 2.0;
+    match sides {
+        4 => polygon(cx, cy + radius * 0.15, radius, 3, up),
+        6 => polygon(cx, cy, radius * 1.1, 4, PI / 4.0),
+        8 => polygon(cx, cy, radius, 4, up),
+        10 => {
+            let mut path = PathBuilder::new();
+            path.move_to(cx, cy - radius);
+            path.line_to(cx + radius * 0.9, cy - radius * 0.1);
+            path.line_to(cx, cy + radius);
+            path.line_to(cx - radius * 0.9, cy - radius * 0.1);
+            path.close();
+            path.finish().expect("Kites have corners")
+        }
+        12 => polygon(cx, cy, radius, 5, up),
+        20 => polygon(cx, cy, radius, 6, up),
+        _ => PathBuilder::from_circle(cx, cy, radius * 0.9).expect("The radius is positive"),
+    }
+}
+
+fn draw_die(pixmap: &mut Pixmap, die: &Die, kept: bool, left: f32, top: f32) {
+    let sides = u8::from(die.sides());
+    let (cx, cy, radius) = (left + TILE / 2.0, top + TILE / 2.0, TILE / 2.0 - 2.0);
+    let colour = match die.colour() {
+        _ if !kept => DROPPED,
+        Some(Colour::Green) => GREEN,
+        Some(Colour::Red) => RED,
+        None => NEUTRAL,
+    };
+    pixmap.fill_path(
+        &shape(sides, cx, cy, radius),
+        &paint(colour),
+        tiny_skia::FillRule::Winding,
+        Transform::identity(),
+        None,
+    );
+
+    // Small triangles leave less room for the number
+    let (room, cy) = if sides == 4 {
+        (radius * 0.8, cy + radius * 0.2)
+    } else {
+        (radius * 1.2, cy)
+    };
+    let value = die.value().to_string();
+    let height = f32::min(radius * 0.6, room / text_width(&value, 1.0));
+    draw_text(
+        pixmap,
+        &value,
+        cx - text_width(&value, height) / 2.0,
+        cy - height / 2.0,
+        height,
+    );
+}
+
+fn text_width(text: &str, height: f32) -> f32 {
+    let count = text.chars().count() as f32;
+    count * height * 0.5 + (count - 1.0).max(0.0) * height * 0.25
+}
+
+/// Draw digits and signs like a seven-segment display, so no font has to be shipped.
+fn draw_text(pixmap: &mut Pixmap, text: &str, left: f32, top: f32, height: f32) {
+    let width = height * 0.5;
+    let mut path = PathBuilder::new();
+    for (i, c) in text.chars().enumerate() {
+        let x = left + i as f32 * (width + height * 0.25);
+        let (middle, bottom, right) = (top + height / 2.0, top + height, x + width);
+        let mut line = |(x0, y0): (f32, f32), (x1, y1): (f32, f32)| {
+            path.move_to(x0, y0);
+            path.line_to(x1, y1);
+        };
+        // Segments from the top one clockwise, then the middle one
+        let segments: &[u8] = match c {
+            '0' => b"abcdef",
+            '1' => b"bc",
+            '2' => b"abged",
+            '3' => b"abgcd",
+            '4' => b"fgbc",
+            '5' => b"afgcd",
+            '6' => b"afgedc",
+            '7' => b"abc",
+            '8' => b"abcdefg",
+            '9' => b"abcdfg",
+            '-' | '+' => b"g",
+            _ => b"",
+        };
+        for segment in segments {
+            match segment {
+                b'a' => line((x, top), (right, top)),
+                b'b' => line((right, top), (right, middle)),
+                b'c' => line((right, middle), (right, bottom)),
+                b'd' => line((x, bottom), (right, bottom)),
+                b'e' => line((x, middle), (x, bottom)),
+                b'f' => line((x, top), (x, middle)),
+                _ => line((x, middle), (right, middle)),
+            }
+        }
+        match c {
+            '+' => line(
+                (x + width / 2.0, middle - width / 2.0),
+                (x + width / 2.0, middle + width / 2.0),
+            ),
+            '=' => {
+                line((x, middle - height * 0.15), (right, middle - height * 0.15));
+                line((x, middle + height * 0.15), (right, middle + height * 0.15));
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(path) = path.finish() {
+        let stroke = Stroke {
+            width: height * 0.12,
+            line_cap: LineCap::Round,
+            ..Stroke::default()
+        };
+        pixmap.stroke_path(&path, &paint(INK), &stroke, Transform::identity(), None);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use super::*;
+    use lang::{SeededRng, Tokens};
+
+    #[test]
+    fn png_works() {
+        let rolled = "4d6:adv3 + d20 + d4 + d8 + d10 + d12 + d100 - 3"
+            .parse::<Tokens>()
+            .unwrap()
+            .roll(&mut SeededRng::seed_from_u64(0));
+        let png = png(&rolled).unwrap();
+
+        let pixmap = Pixmap::decode_png(&png).unwrap();
+        // Fourteen dice and seven signs take two rows
+        assert!(
+            pixmap.width() as f32 <= 2.0 * MARGIN + TILES_PER_ROW as f32 * (TILE + GAP),
+            "{}",
+            pixmap.width()
+        );
+        assert_eq!(
+            pixmap.height(),
+            (2.0 * MARGIN + 2.0 * (TILE + GAP) + GAP + TOTAL_HEIGHT).ceil() as u32
+        );
+
+        // The dropped d6 is filled in grey, away from its number
+        let thrown = rolled.iter().next().unwrap().thrown();
+        let colour = |i: usize| {
+            let left = MARGIN + i as f32 * (TILE + GAP);
+            let (x, y) = (left + TILE / 2.0 - 20.0, MARGIN + TILE / 2.0 - 20.0);
+            let pixel = pixmap.pixel(x as u32, y as u32).unwrap();
+            (pixel.red(), pixel.green(), pixel.blue())
+        };
+        for (i, (_, kept)) in thrown.into_iter().enumerate() {
+            assert_eq!(colour(i) == DROPPED, !kept, "die {i}");
+        }
+    }
+
+    #[test]
+    fn png_caps_rows() {
+        let rolled = format!("{}d6", TILES_PER_ROW * MAX_ROWS + 1)
+            .parse::<Tokens>()
+            .unwrap()
+            .roll(&mut SeededRng::seed_from_u64(0));
+        assert_eq!(png(&rolled), None);
+    }
+}
@@ -0,0 +1,419 @@
+mod image;
+
+use itertools::Itertools;
+use poise::{
+    serenity_prelude::{CreateAttachment, CreateEmbed, CreateEmbedFooter},
+    ChoiceParameter, CreateReply,
+};
+use serde::{Deserialize, Serialize};
+
+use lang::{Outcome, RolledToken, Token};
+use locale::{Locale, Localize};
+use roll_log::Roll;
+use table::Table;
+
+use crate::command::*;
+
+/// Discord allows at most 25 fields per embed.
+const MAX_FIELDS: usize = 25;
+/// Discord allows at most 1024 characters per field value.
+const MAX_FIELD_LENGTH: usize = 1024;
+/// Discord allows at most 256 characters per embed title.
+const MAX_TITLE_LENGTH: usize = 256;
+/// Discord allows at most 6000 characters across the title, description, fields and footer.
+const MAX_EMBED_LENGTH: usize = 6000;
+
+const CRITICAL_COLOUR: u32 = 0x2ecc71;
+const FUMBLE_COLOUR: u32 = 0xe74c3c;
+const NEUTRAL_COLOUR: u32 = 0x5865f2;
+
+/// How a roll is posted.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize, poise::ChoiceParameter,
+)]
+pub enum Output {
+    /// The table in a code block.
+    #[default]
+    #[name = "Text"]
+    #[name_localized("fr", "Texte")]
+    Text,
+    /// An embed with a field per term and the total in large text.
+    #[name = "Embed"]
+    #[name_localized("fr", "Intégration")]
+    Embed,
+    /// A single line with the dice and the total.
+    #[name = "Inline"]
+    #[name_localized("fr", "En ligne")]
+    Inline,
+    /// A picture of the dice in their own shapes, with the single line as text.
+    #[name = "Image"]
+    #[name_localized("fr", "Image")]
+    Image,
+}
+
+impl Localize for Output {
+    fn localize(&self, locale: Locale) -> String {
+        self.localized_name(locale.discord())
+            .unwrap_or(self.name())
+            .to_string()
+    }
+}
+
+impl Output {
+    /// Reply posting `roll`, with its ID when it was just logged.
+    pub async fn render(
+        self,
+        roll: &Roll,
+        id: Option<&str>,
+    ) -> std::result::Result<CreateReply, Error> {
+        Ok(match self {
+            Output::Text => CreateReply::default().content(text(roll, table(roll).await?, id)),
+            Output::Embed => CreateReply::default().embed(embed(roll, stats(roll).await?, id)),
+            Output::Inline => CreateReply::default().content(inline(roll, id)),
+            Output::Image => {
+                let reply = CreateReply::default().content(inline(roll, id));
+                // Drawing hundreds of dice takes a while
+                let rolled = roll.rolled.clone();
+                match tokio::task::spawn_blocking(move || image::png(&rolled)).await? {
+                    Some(png) => reply.attachment(CreateAttachment::bytes(png, "roll.png")),
+                    None => reply,
+                }
+            }
+        })
+    }
+}
+
+fn footer(roll: &Roll, id: Option<&str>) -> Vec<String> {
+    let catalog = roll.locale.catalog();
+    let mut footer = vec![catalog.fair_roll(
+        roll.fair.day,
+        &roll.fair.commitment[..16],
+        &roll.fair.client_seed,
+        roll.fair.nonce,
+    )];
+    footer.extend(id.map(|id| catalog.roll_id(id)));
+    footer
+}
+
+/// Stats of a roll when they were asked for.
+///
+/// The exact distribution can take a few seconds, so it is computed on a blocking thread.
+async fn stats(roll: &Roll) -> std::result::Result<Option<Table>, Error> {
+    if !roll.stats {
+        return Ok(None);
+    }
+    let (rolled, locale) = (roll.rolled.clone(), roll.locale);
+    let stats = tokio::task::spawn_blocking(move || {
+        rolled.stats_table(rolled.tokens().distribution().as_ref(), locale)
+    })
+    .await?;
+    Ok(Some(stats))
+}
+
+/// Dice of a roll, and their stats when they were asked for.
+pub async fn table(roll: &Roll) -> std::result::Result<Table, Error> {
+    let mut table = roll
+        .rolled
+        .table(roll.locale, usize::from(roll.pool_threshold));
+    if let Some(stats) = stats(roll).await? {
+        table.append_table(&stats);
+    }
+    Ok(table)
+}
+
+fn text(roll: &Roll, table: Table, id: Option<&str>) -> String {
+    // Coloured dice need an ansi block, which loses the highlighting of `//` comments
+    let (language, table) = if table.is_coloured() {
+        ("ansi", table.render_ansi(roll.style))
+    } else {
+        ("rust", table.render(roll.style))
+    };
+    format!(
+        "\
+```{language}
+{table}
+```
+{}",
+        footer(roll, id)
+            .iter()
+            .map(|line| format!("-# {line}"))
+            .join("\n")
+    )
+}
+
+/// Dice of a term, the dropped ones struck through, or face counts like `3×7` for pools of more
+/// than `pool_threshold` dice.
+fn dice(token: &RolledToken, pool_threshold: usize) -> String {
+    let strike = |text: String, kept: bool| {
+        if kept {
+            text
+        } else {
+            format!("~~{text}~~")
+        }
+    };
+    let thrown = token.thrown();
+    if thrown.len() > pool_threshold {
+        thrown
+            .into_iter()
+            .map(|(die, kept)| (!kept, die.value()))
+            .counts()
+            .into_iter()
+            .sorted()
+            .map(|((dropped, face), count)| strike(format!("{face}×{count}"), !dropped))
+            .join(", ")
+    } else {
+        thrown
+            .into_iter()
+            .map(|(die, kept)| strike(die.value().to_string(), kept))
+            .join(", ")
+    }
+}
+
+/// One line like `` `1d20 + 5` → [14] + 5 = **19** ``, without stats or seeds.
+fn inline(roll: &Roll, id: Option<&str>) -> String {
+    let mut terms = String::new();
+    for (i, token) in roll.rolled.iter().enumerate() {
+        let term = match token {
+            RolledToken::Die(..) => format!("[{}]", dice(token, usize::from(roll.pool_threshold))),
+            RolledToken::Constant(value) if i > 0 => value.unsigned_abs().to_string(),
+            RolledToken::Constant(value) => value.to_string(),
+        };
+        match token {
+            _ if i == 0 => {}
+            RolledToken::Constant(value) if value.is_negative() => terms += " - ",
+            _ => terms += " + ",
+        }
+        terms += &term;
+    }
+
+    let mut line = format!(
+        "`{}` → {terms} = **{}**",
+        roll.rolled.tokens(),
+        roll.rolled.value()
+    );
+    if let Some(id) = id {
+        line += &format!("\n-# {}", roll.locale.catalog().roll_id(id));
+    }
+    line
+}
+
+/// `text` cut to `max` characters, ending with `…` when it was cut.
+fn truncate(text: String, max: usize) -> String {
+    if text.chars().count() > max {
+        let mut text = text.chars().take(max - 1).collect::<String>();
+        text.push('…');
+        text
+    } else {
+        text
+    }
+}
+
+/// Dice of a term and its subtotal, cut to fit in a field.
+fn field_value(token: &RolledToken, pool_threshold: usize) -> String {
+    let value = match token {
+        RolledToken::Die(..) => format!("{} = **{}**", dice(token, pool_threshold), token.value()),
+        RolledToken::Constant(_) => format!("**{}**", token.value()),
+    };
+    truncate(value, MAX_FIELD_LENGTH)
+}
+
+fn embed(roll: &Roll, stats: Option<Table>, id: Option<&str>) -> CreateEmbed {
+    let catalog = roll.locale.catalog();
+    let colour = match roll.rolled.outcome() {
+        Some(Outcome::Critical) => CRITICAL_COLOUR,
+        Some(Outcome::Fumble) => FUMBLE_COLOUR,
+        None => NEUTRAL_COLOUR,
+    };
+    let title = truncate(roll.rolled.tokens().to_string(), MAX_TITLE_LENGTH);
+    let description = format!("# {}", roll.rolled.value());
+    let footer = footer(roll, id).join("\n");
+    let stats = stats.map(|stats| {
+        (
+            catalog.stats().to_string(),
+            format!("```rust\n{}\n```", stats.render(roll.style)),
+            false,
+        )
+    });
+
+    // Terms that do not fit are counted in a last field instead
+    let mut terms = roll
+        .rolled
+        .iter()
+        .map(|token| {
+            (
+                Token::from(token).to_string(),
+                field_value(token, usize::from(roll.pool_threshold)),
+                true,
+            )
+        })
+        .collect::<Vec<_>>();
+    let mut hidden = 0;
+    let fields = loop {
+        let fields = terms
+            .iter()
+            .cloned()
+            .chain((hidden > 0).then(|| ("…".to_string(), catalog.more_terms(hidden), false)))
+            .chain(stats.clone())
+            .collect::<Vec<_>>();
+        let length = [&title, &description, &footer]
+            .into_iter()
+            .chain(fields.iter().flat_map(|(name, value, _)| [name, value]))
+            .map(|text| text.chars().count())
+            .sum::<usize>();
+        if terms.is_empty() || (fields.len() <= MAX_FIELDS && length <= MAX_EMBED_LENGTH) {
+            break fields;
+        }
+        terms.pop();
+        hidden += 1;
+    };
+
+    CreateEmbed::new()
+        .title(title)
+        .description(description)
+        .colour(colour)
+        .fields(fields)
+        .footer(CreateEmbedFooter::new(footer))
+}
+
+#[cfg(test)]
+mod test {
+    use std::num::{NonZeroI16, NonZeroU8};
+
+    use super::*;
+    use lang::{Die, Modifier, RolledTokens};
+
+    fn die(sides: u8, value: i16) -> Die {
+        Die::new(NonZeroU8::new(sides).unwrap(), value)
+    }
+
+    #[test]
+    fn text_works() {
+        let roll = Roll::test("d20");
+        let text = text(&roll, roll.rolled.table(roll.locale, 12), Some("abc234"));
+        assert!(text.starts_with("```rust\n"), "{text}");
+        assert!(
+            text.ends_with("\n-# Roll `abc234`, show it again with `/replay abc234`."),
+            "{text}"
+        );
+        assert_eq!(
+            text.lines().filter(|line| line.starts_with("-# ")).count(),
+            2
+        );
+    }
+
+    #[test]
+    fn inline_works() {
+        let mut roll = Roll::test("d20 + 5");
+        roll.rolled = RolledTokens::from(vec![
+            RolledToken::Die(vec![die(20, 14)], None),
+            RolledToken::Constant(NonZeroI16::new(5).unwrap()),
+        ]);
+        assert_eq!(inline(&roll, None), "`1d20 + 5` → [14] + 5 = **19**");
+
+        roll.rolled = RolledTokens::from(vec![
+            RolledToken::Constant(NonZeroI16::new(-2).unwrap()),
+            RolledToken::Die(
+                vec![die(6, 2), die(6, 5)],
+                Some(Modifier::Disadvantage(NonZeroU8::new(1).unwrap())),
+            ),
+            RolledToken::Constant(NonZeroI16::new(-1).unwrap()),
+        ]);
+        assert_eq!(
+            inline(&roll, Some("abc234")),
+            "`-2 + 2d6:dis1 - 1` → -2 + [2, ~~5~~] - 1 = **-1**\n\
+            -# Roll `abc234`, show it again with `/replay abc234`."
+        );
+
+        roll.pool_threshold = 4;
+        roll.rolled = RolledTokens::from(vec![RolledToken::Die(
+            [1, 6, 3, 6, 1, 2]
+                .into_iter()
+                .map(|value| die(6, value))
+                .collect(),
+            Some(Modifier::Advantage(NonZeroU8::new(5).unwrap())),
+        )]);
+        assert_eq!(
+            inline(&roll, None),
+            "`6d6:adv5` → [1×1, 2×1, 3×1, 6×2, ~~1×1~~] = **18**"
+        );
+    }
+
+    #[test]
+    fn field_value_works() {
+        let roll = Roll::test("3d6 + 4");
+        let values = roll
+            .rolled
+            .iter()
+            .map(|token| field_value(token, 12))
+            .collect::<Vec<_>>();
+        let dice = match roll.rolled.iter().next().unwrap() {
+            RolledToken::Die(dice, _) => dice.iter().map(|die| die.value()).join(", "),
+            RolledToken::Constant(_) => unreachable!(),
+        };
+        assert_eq!(
+            values,
+            vec![
+                format!("{dice} = **{}**", roll.rolled.value() - 4),
+                "**4**".to_string()
+            ]
+        );
+
+        let long = RolledToken::Die(vec![die(100, 100); 300], None);
+        let value = field_value(&long, usize::MAX);
+        assert_eq!(value.chars().count(), MAX_FIELD_LENGTH);
+        assert!(value.ends_with('…'));
+    }
+
+    #[test]
+    fn embed_fits_limits() {
+        let embed = |roll: &Roll| serde_json::to_value(embed(roll, None, Some("abc234"))).unwrap();
+        let names = |embed: &serde_json::Value| {
+            embed["fields"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|field| field["name"].as_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let short = embed(&Roll::test("d20 + 5"));
+        assert_eq!(names(&short), vec!["1d20", "5"]);
+
+        // Thirty terms take more fields than an embed has
+        let mut many = Roll::test("d20");
+        many.rolled = RolledTokens::from(vec![RolledToken::Die(vec![die(6, 3)], None); 30]);
+        let many = embed(&many);
+        let names = names(&many);
+        assert_eq!(names.len(), MAX_FIELDS);
+        assert_eq!(names.last().unwrap(), "…");
+        assert_eq!(many["fields"][MAX_FIELDS - 1]["value"], "And 6 more terms");
+        assert!(many["title"].as_str().unwrap().chars().count() <= MAX_TITLE_LENGTH);
+
+        // Thirty big pools take more characters than an embed has
+        let mut long = Roll::test("d20");
+        long.pool_threshold = u16::MAX;
+        long.rolled =
+            RolledTokens::from(vec![RolledToken::Die(vec![die(100, 100); 255], None); 30]);
+        let long = embed(&long);
+        let length = ["title", "description"]
+            .into_iter()
+            .map(|key| long[key].as_str().unwrap().chars().count())
+            .chain(long["fields"].as_array().unwrap().iter().map(|field| {
+                field["name"].as_str().unwrap().chars().count()
+                    + field["value"].as_str().unwrap().chars().count()
+            }))
+            .sum::<usize>()
+            + long["footer"]["text"].as_str().unwrap().chars().count();
+        assert!(length <= MAX_EMBED_LENGTH, "{length}");
+        assert_eq!(
+            long["title"].as_str().unwrap().chars().count(),
+            MAX_TITLE_LENGTH
+        );
+        let fields = long["fields"].as_array().unwrap();
+        assert!(fields.len() < MAX_FIELDS);
+        assert!(fields.last().unwrap()["value"]
+            .as_str()
+            .unwrap()
+            .ends_with("more terms"));
+    }
+}
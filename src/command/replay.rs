@@ -0,0 +1,48 @@
+use locale::Locale;
+use poise::{serenity_prelude::CreateAllowedMentions, CreateReply};
+
+use crate::command::*;
+
+/// Show a past roll again, exactly as it was posted.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    name_localized("fr", "revoir"),
+    description_localized("fr", "Afficher à nouveau un lancer passé, tel qu'il a été posté.")
+)]
+pub async fn replay(
+    ctx: Context<'_>,
+    #[description = "ID of the roll, shown under it"]
+    #[name_localized("fr", "id")]
+    #[description_localized("fr", "Identifiant du lancer, affiché en dessous")]
+    id: String,
+) -> Result {
+    let catalog = Locale::of(ctx).catalog();
+
+    let logged = ctx
+        .data()
+        .log
+        .lock()
+        .expect("Log is not poisoned")
+        .get(&id)
+        .cloned();
+    let reply = match logged {
+        Some(logged) => {
+            let header = catalog.replay_header(
+                &logged.id,
+                logged.roll.user.get(),
+                logged.roll.channel.get(),
+                logged.time,
+            );
+            let mut reply = logged.roll.output.render(&logged.roll, None).await?;
+            reply.content = Some(match reply.content {
+                Some(content) => format!("{header}\n{content}"),
+                None => header,
+            });
+            reply
+        }
+        None => CreateReply::default().content(catalog.replay_not_found(&id)),
+    };
+    // Show who rolled without pinging them
+    send(ctx, reply.allowed_mentions(CreateAllowedMentions::new())).await
+}
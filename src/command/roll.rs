@@ -1,31 +1,79 @@
 use lang::Tokens;
-use table::Table;
+use locale::Locale;
+use render::Output;
+use roll_log::Roll;
+use table::Style;
 
 use crate::command::*;
 
+fn help_text() -> String {
+    Locale::English.catalog().roll_help().to_string()
+}
+
 /// Roll some dice.
-///
-/// Valid tokens are:
-/// - `1` - constant, bonus
-/// - `d20` - 20-sided die
-/// - `4d6` - 4 6-sided dice
-/// - `2d20:adv` - 2 20-sided dice, pick highest
-/// - `4d6:dis3` - 4 6-sided dice, pick 3 lowest
-#[poise::command(slash_command, prefix_command)]
+#[poise::command(
+    slash_command,
+    prefix_command,
+    help_text_fn = "help_text",
+    name_localized("fr", "lancer"),
+    description_localized("fr", "Lancer des dés.")
+)]
 pub async fn roll(
     ctx: Context<'_>,
-    #[description = "What to roll, space separated"] tokens: Tokens,
+    #[description = "What to roll, space separated"]
+    #[name_localized("fr", "elements")]
+    #[description_localized("fr", "Ce qu'il faut lancer, séparé par des espaces")]
+    tokens: Tokens,
+    #[description = "Show the min, max and mean of the roll, and where the total lands"]
+    #[name_localized("fr", "statistiques")]
+    #[description_localized(
+        "fr",
+        "Afficher le min, le max et la moyenne du lancer, et où se situe le total"
+    )]
+    stats: Option<bool>,
+    #[description = "How to draw the table, the style of the server by default"]
+    #[description_localized("fr", "Comment dessiner le tableau, le style du serveur par défaut")]
+    style: Option<Style>,
+    #[description = "Table, embed, single line or image, the output of the server by default"]
+    #[description_localized(
+        "fr",
+        "Tableau, intégration, ligne ou image, l'affichage du serveur par défaut"
+    )]
+    output: Option<Output>,
 ) -> Result {
-    let table: Table = tokens.into();
+    let limits = &ctx.data().limits;
+    tokens.check_limits(limits)?;
 
-    ctx.say(format!(
-        "\
-```rust
-{table}
-```
-"
-    ))
-    .await?;
+    let (fair, rolled) = {
+        let mut seeds = ctx.data().seeds.lock().expect("Seeds are not poisoned");
+        let (fair, mut rng) = seeds.next(ctx.author().id);
+        (fair, tokens.roll(&mut rng))
+    };
+    ctx.data()
+        .faces
+        .lock()
+        .expect("Faces are not poisoned")
+        .record(&rolled);
 
-    Ok(())
+    let settings = ctx.data().guild_settings(ctx.guild_id());
+    let roll = Roll {
+        user: ctx.author().id,
+        channel: ctx.channel_id(),
+        input: tokens.to_string(),
+        fair,
+        rolled,
+        stats: stats.unwrap_or_default(),
+        locale: Locale::of(ctx),
+        style: style.unwrap_or(settings.style),
+        output: output.unwrap_or(settings.output),
+        pool_threshold: settings.pool_threshold,
+    };
+    let id = ctx
+        .data()
+        .log
+        .lock()
+        .expect("Log is not poisoned")
+        .record(roll.clone());
+    let reply = roll.output.render(&roll, Some(&id)).await?;
+    send(ctx, reply).await
 }
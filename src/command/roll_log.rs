@@ -0,0 +1,273 @@
+use std::{
+    collections::HashMap,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use poise::serenity_prelude::{ChannelId, UserId};
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+
+use lang::RolledTokens;
+use locale::Locale;
+use render::Output;
+use seeds::FairRoll;
+use table::Style;
+
+use crate::command::*;
+
+/// Characters of roll IDs, without the ones that are easy to mix up.
+const ID_ALPHABET: &[u8] = b"abcdefghjkmnpqrstuvwxyz23456789";
+const ID_LENGTH: usize = 6;
+
+/// A roll as it was posted, enough to show it again.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Roll {
+    pub user: UserId,
+    pub channel: ChannelId,
+    pub input: String,
+    pub fair: FairRoll,
+    pub rolled: RolledTokens,
+    pub stats: bool,
+    pub locale: Locale,
+    /// Rolls logged before styles existed were drawn in the default one.
+    #[serde(default)]
+    pub style: Style,
+    /// Rolls logged before embeds existed were posted as text.
+    #[serde(default)]
+    pub output: Output,
+    /// Rolls logged before pools were collapsed are shown with the default threshold.
+    #[serde(default = "pool_threshold")]
+    pub pool_threshold: u16,
+}
+
+fn pool_threshold() -> u16 {
+    settings::POOL_THRESHOLD
+}
+
+#[cfg(test)]
+impl Roll {
+    /// `input` rolled with a fixed seed, with plain settings.
+    pub fn test(input: &str) -> Self {
+        use lang::{SeededRng, Tokens};
+        use rand::SeedableRng;
+
+        let tokens = input.parse::<Tokens>().expect("Test rolls are valid");
+        Self {
+            user: UserId::new(1),
+            channel: ChannelId::new(2),
+            input: tokens.to_string(),
+            fair: FairRoll {
+                day: 10,
+                commitment: "0123456789abcdef0123".to_string(),
+                client_seed: "1".to_string(),
+                nonce: 0,
+            },
+            rolled: tokens.roll(&mut SeededRng::seed_from_u64(0)),
+            stats: false,
+            locale: Locale::English,
+            style: Style::Ascii,
+            output: Output::Text,
+            pool_threshold: settings::POOL_THRESHOLD,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct LoggedRoll {
+    pub id: String,
+    /// Seconds since the epoch.
+    pub time: u64,
+    #[serde(flatten)]
+    pub roll: Roll,
+}
+
+/// Every roll made with `/roll`, appended as JSON lines to a file so it outlives restarts.
+pub struct RollLog {
+    file: Option<File>,
+    rolls: HashMap<String, LoggedRoll>,
+}
+
+fn id<R>(rng: &mut R) -> String
+where
+    R: Rng + ?Sized,
+{
+    (0..ID_LENGTH)
+        .map(|_| *ID_ALPHABET.choose(rng).expect("The alphabet is not empty") as char)
+        .collect()
+}
+
+impl RollLog {
+    /// Load the rolls logged in `path` and append the next ones to it.
+    ///
+    /// Lines that cannot be read, like one cut short by a crash, or with dice that cannot have
+    /// been rolled are skipped with a warning.
+    pub fn open(path: PathBuf) -> io::Result<Self> {
+        let mut rolls = HashMap::new();
+        let mut complete = true;
+        if path.exists() {
+            let content = fs::read_to_string(&path)?;
+            complete = content.is_empty() || content.ends_with('\n');
+            for (number, line) in content.lines().enumerate() {
+                match serde_json::from_str::<LoggedRoll>(line) {
+                    Ok(roll) if roll.roll.rolled.is_valid() => {
+                        rolls.insert(roll.id.clone(), roll);
+                    }
+                    Ok(roll) => tracing::warn!(
+                        "Skipping line {} of the roll log `{}`: roll `{}` has impossible dice",
+                        number + 1,
+                        path.display(),
+                        roll.id
+                    ),
+                    Err(e) => tracing::warn!(
+                        "Skipping line {} of the roll log `{}`: {e}",
+                        number + 1,
+                        path.display()
+                    ),
+                }
+            }
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        // Start the next roll on its own line after a line cut short
+        if !complete {
+            writeln!(file)?;
+        }
+
+        Ok(Self {
+            file: Some(file),
+            rolls,
+        })
+    }
+
+    /// A log that forgets everything on restart.
+    pub fn in_memory() -> Self {
+        Self {
+            file: None,
+            rolls: HashMap::new(),
+        }
+    }
+
+    /// Log a roll under a new ID and return it.
+    ///
+    /// A roll that cannot be written stays available until the bot restarts.
+    pub fn record(&mut self, roll: Roll) -> String {
+        let mut rng = rand::thread_rng();
+        let id = std::iter::repeat_with(|| id(&mut rng))
+            .find(|id| !self.rolls.contains_key(id))
+            .expect("IDs are endless");
+        let roll = LoggedRoll {
+            id: id.clone(),
+            time: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("The clock is after the epoch")
+                .as_secs(),
+            roll,
+        };
+
+        if let Some(file) = &mut self.file {
+            let written = serde_json::to_string(&roll)
+                .map_err(io::Error::from)
+                .and_then(|json| writeln!(file, "{json}"));
+            if let Err(e) = written {
+                tracing::error!("Error while logging roll `{id}`: {e}");
+            }
+        }
+        self.rolls.insert(id.clone(), roll);
+
+        id
+    }
+
+    pub fn get(&self, id: &str) -> Option<&LoggedRoll> {
+        self.rolls.get(&id.trim().to_lowercase())
+    }
+
+    /// Every logged roll, in no particular order.
+    pub fn rolls(&self) -> impl Iterator<Item = &LoggedRoll> {
+        self.rolls.values()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use super::*;
+    use lang::SeededRng;
+
+    fn record(log: &mut RollLog, input: &str) -> String {
+        log.record(Roll {
+            stats: true,
+            locale: Locale::French,
+            style: Style::Unicode,
+            output: Output::Embed,
+            ..Roll::test(input)
+        })
+    }
+
+    #[test]
+    fn id_works() {
+        let id = id(&mut SeededRng::seed_from_u64(0));
+        assert_eq!(id.len(), ID_LENGTH);
+        assert!(id.bytes().all(|c| ID_ALPHABET.contains(&c)), "{id}");
+    }
+
+    #[test]
+    fn record_works() {
+        let mut log = RollLog::in_memory();
+        let first = record(&mut log, "4d6:adv3 + 2");
+        let second = record(&mut log, "d20");
+        assert_ne!(first, second);
+
+        let roll = log.get(&first.to_uppercase()).unwrap();
+        assert_eq!(roll.roll.input, "4d6:adv3 + 2");
+        assert_eq!(roll.roll.rolled.tokens().to_string(), "4d6:adv3 + 2");
+        assert_eq!(log.get("nope"), None);
+    }
+
+    #[test]
+    fn open_reloads_rolls() {
+        let path = store::temp_path("rolls", "jsonl");
+        let id = record(&mut RollLog::open(path.clone()).unwrap(), "2d20:dis - 1");
+
+        let log = RollLog::open(path.clone()).unwrap();
+        let roll = log.get(&id).unwrap();
+        assert_eq!(roll.roll.rolled.tokens().to_string(), "2d20:dis1 - 1");
+        assert_eq!(
+            (
+                roll.roll.stats,
+                roll.roll.locale,
+                roll.roll.style,
+                roll.roll.output
+            ),
+            (true, Locale::French, Style::Unicode, Output::Embed)
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn open_skips_corrupt_lines() {
+        let path = store::temp_path("rolls", "jsonl");
+        let id = record(&mut RollLog::open(path.clone()).unwrap(), "d20");
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        let mut impossible =
+            serde_json::to_value(RollLog::open(path.clone()).unwrap().get(&id).unwrap()).unwrap();
+        impossible["id"] = "zzzzzz".into();
+        impossible["rolled"][0]["Die"][0][0]["value"] = 0.into();
+        writeln!(file, "{impossible}").unwrap();
+        write!(file, "not json\n{{\"id\":\"cut").unwrap();
+
+        let mut log = RollLog::open(path.clone()).unwrap();
+        assert!(log.get(&id).is_some());
+        assert!(log.get("zzzzzz").is_none());
+        let next = record(&mut log, "d6");
+
+        let log = RollLog::open(path.clone()).unwrap();
+        assert!(log.get(&id).is_some());
+        assert!(log.get(&next).is_some());
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,63 @@
+use locale::Locale;
+
+use crate::command::*;
+
+/// Show the hash of today's server seed and reveal the seeds of past days.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    name_localized("fr", "graine"),
+    description_localized(
+        "fr",
+        "Afficher le hachage de la graine du jour et révéler celles des jours passés."
+    )
+)]
+pub async fn seed(
+    ctx: Context<'_>,
+    #[description = "Day to reveal the server seed of, the last one by default"]
+    #[name_localized("fr", "jour")]
+    #[description_localized("fr", "Jour dont la graine est révélée, le dernier par défaut")]
+    day: Option<u64>,
+    #[description = "New client seed for your rolls, up to 64 letters, digits, `_` or `-`"]
+    #[name_localized("fr", "graine_client")]
+    #[description_localized(
+        "fr",
+        "Nouvelle graine client pour vos lancers, 64 lettres, chiffres, `_` ou `-` au plus"
+    )]
+    client_seed: Option<String>,
+) -> Result {
+    let catalog = Locale::of(ctx).catalog();
+    if let Some(client_seed) = &client_seed {
+        if !seeds::valid_client_seed(client_seed) {
+            ctx.say(catalog.seed_client_invalid(seeds::MAX_CLIENT_SEED_LENGTH))
+                .await?;
+            return Ok(());
+        }
+    }
+
+    let reply = {
+        let mut seeds = ctx.data().seeds.lock().expect("Seeds are not poisoned");
+        if let Some(client_seed) = client_seed {
+            seeds.set_client_seed(ctx.author().id, client_seed);
+        }
+
+        let (today, commitment) = seeds.commitment();
+        let revealed = match (seeds.revealed(day), day) {
+            (Some((day, seed)), _) => catalog.seed_revealed(day, seed),
+            (None, Some(day)) => catalog.seed_not_revealed(day),
+            (None, None) => catalog.seed_none_revealed(),
+        };
+        let (client_seed, nonce) = seeds.client_seed(ctx.author().id);
+
+        [
+            catalog.seed_commitment(today, &commitment),
+            revealed,
+            catalog.seed_client(&client_seed, nonce),
+        ]
+        .join("\n")
+    };
+
+    ctx.say(reply).await?;
+
+    Ok(())
+}
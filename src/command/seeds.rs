@@ -0,0 +1,306 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs, io,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use poise::serenity_prelude::UserId;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use lang::{commitment, fair_rng, SeededRng};
+
+use crate::command::*;
+
+/// Nonces handed out between two saves. A restart skips the ones that were not used yet, so
+/// rolls do not write the file every time and no nonce is ever handed out twice.
+const NONCE_BATCH: u64 = 100;
+
+/// Client seeds are shown under every roll, so they are kept short and plain.
+pub const MAX_CLIENT_SEED_LENGTH: usize = 64;
+
+/// Whether `client_seed` is up to [`MAX_CLIENT_SEED_LENGTH`] ASCII letters, digits, `_` or `-`.
+pub fn valid_client_seed(client_seed: &str) -> bool {
+    (1..=MAX_CLIENT_SEED_LENGTH).contains(&client_seed.len())
+        && client_seed
+            .bytes()
+            .all(|c| c.is_ascii_alphanumeric() || c == b'_' || c == b'-')
+}
+
+/// Server seeds of provably fair rolls, rotated daily.
+///
+/// Only the hash of the seed of the current day is public, the seed itself is revealed once the
+/// day is over so every roll made with it can be recomputed. Seeds and nonces are saved as JSON
+/// so a restart neither loses the committed seed nor hands out a nonce again, nonces ahead by
+/// up to [`NONCE_BATCH`].
+#[derive(Serialize, Deserialize)]
+pub struct Seeds {
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    day: u64,
+    server_seed: String,
+    revealed: BTreeMap<u64, String>,
+    players: HashMap<UserId, Player>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Player {
+    client_seed: String,
+    /// Nonce of the next roll.
+    #[serde(skip)]
+    nonce: u64,
+    /// Nonce the player starts from after a restart, the nonces up to it may already be used.
+    #[serde(rename = "nonce")]
+    reserved: u64,
+}
+
+/// Everything a player needs to recompute a roll.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct FairRoll {
+    pub day: u64,
+    pub commitment: String,
+    pub client_seed: String,
+    pub nonce: u64,
+}
+
+fn today() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("The clock is after the epoch")
+        .as_secs()
+        / (24 * 60 * 60)
+}
+
+fn server_seed() -> String {
+    rand::thread_rng()
+        .gen::<[u8; 32]>()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+impl Seeds {
+    /// Load the seeds saved in `path`, or start new ones, and save the next changes to it.
+    pub fn open(path: PathBuf) -> io::Result<Self> {
+        let mut seeds: Self = if path.exists() {
+            serde_json::from_str(&fs::read_to_string(&path)?)?
+        } else {
+            Self::starting(today())
+        };
+        for player in seeds.players.values_mut() {
+            player.nonce = player.reserved;
+        }
+        seeds.path = Some(path);
+        seeds.save();
+
+        Ok(seeds)
+    }
+
+    fn starting(day: u64) -> Self {
+        Self {
+            path: None,
+            day,
+            server_seed: server_seed(),
+            revealed: BTreeMap::new(),
+            players: HashMap::new(),
+        }
+    }
+
+    /// Reveal the seed of the previous day and start counting rolls again with a new one.
+    fn rotate(&mut self, today: u64) {
+        if today != self.day {
+            let seed = std::mem::replace(&mut self.server_seed, server_seed());
+            self.revealed.insert(self.day, seed);
+            self.day = today;
+            for player in self.players.values_mut() {
+                player.nonce = 0;
+                player.reserved = 0;
+            }
+            self.save();
+        }
+    }
+
+    /// A seed that cannot be written stays in use until the bot restarts.
+    fn save(&self) {
+        if let Some(path) = &self.path {
+            let written = serde_json::to_string_pretty(self)
+                .map_err(io::Error::from)
+                .and_then(|json| store::write_atomically(path, &json));
+            if let Err(e) = written {
+                tracing::error!("Error while saving seeds: {e}");
+            }
+        }
+    }
+
+    fn player(&mut self, user: UserId) -> &mut Player {
+        self.players.entry(user).or_insert_with(|| Player {
+            client_seed: user.to_string(),
+            nonce: 0,
+            reserved: 0,
+        })
+    }
+
+    /// Day and hash of the current server seed.
+    pub fn commitment(&mut self) -> (u64, String) {
+        self.rotate(today());
+        (self.day, commitment(&self.server_seed))
+    }
+
+    /// Server seed of a past day, or of the last day that is over.
+    pub fn revealed(&mut self, day: Option<u64>) -> Option<(u64, &str)> {
+        self.rotate(today());
+        match day {
+            Some(day) => self.revealed.get_key_value(&day),
+            None => self.revealed.last_key_value(),
+        }
+        .map(|(day, seed)| (*day, seed.as_str()))
+    }
+
+    /// Client seed and nonce of the next roll of a player.
+    pub fn client_seed(&mut self, user: UserId) -> (String, u64) {
+        self.rotate(today());
+        let player = self.player(user);
+        (player.client_seed.clone(), player.nonce)
+    }
+
+    /// Change the client seed of a player.
+    ///
+    /// The nonce keeps counting until the server seed rotates, so setting a client seed used
+    /// before never brings back a roll that was already seen.
+    pub fn set_client_seed(&mut self, user: UserId, client_seed: String) {
+        self.rotate(today());
+        self.player(user).client_seed = client_seed;
+        self.save();
+    }
+
+    /// Use up the next nonce of a player and derive the dice source of the roll.
+    pub fn next(&mut self, user: UserId) -> (FairRoll, SeededRng) {
+        self.next_on(today(), user)
+    }
+
+    fn next_on(&mut self, today: u64, user: UserId) -> (FairRoll, SeededRng) {
+        self.rotate(today);
+        let day = self.day;
+        let server_seed = self.server_seed.clone();
+        let player = self.player(user);
+        let roll = FairRoll {
+            day,
+            commitment: commitment(&server_seed),
+            client_seed: player.client_seed.clone(),
+            nonce: player.nonce,
+        };
+        player.nonce += 1;
+        if player.nonce > player.reserved {
+            player.reserved = player.nonce + NONCE_BATCH;
+            self.save();
+        }
+
+        let rng = fair_rng(&server_seed, &roll.client_seed, roll.nonce);
+        (roll, rng)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn valid_client_seed_works() {
+        for seed in ["1", "lucky_Seed-42", &"a".repeat(MAX_CLIENT_SEED_LENGTH)] {
+            assert!(valid_client_seed(seed), "{seed}");
+        }
+        for seed in [
+            "",
+            "a`b",
+            "with space",
+            "dé",
+            &"a".repeat(MAX_CLIENT_SEED_LENGTH + 1),
+        ] {
+            assert!(!valid_client_seed(seed), "{seed}");
+        }
+    }
+
+    #[test]
+    fn next_counts_rolls() {
+        let mut seeds = Seeds::starting(10);
+        let user = UserId::new(1);
+        assert_eq!(seeds.next_on(10, user).0.nonce, 0);
+        assert_eq!(seeds.next_on(10, user).0.nonce, 1);
+        assert_eq!(seeds.next_on(10, UserId::new(2)).0.nonce, 0);
+        assert_eq!(seeds.next_on(10, user).0.client_seed, "1");
+    }
+
+    #[test]
+    fn rotate_reveals_seed() {
+        let mut seeds = Seeds::starting(10);
+        let user = UserId::new(1);
+        let (roll, mut rng) = seeds.next_on(10, user);
+        let server_seed = seeds.server_seed.clone();
+
+        seeds.next_on(11, user);
+        assert_eq!(seeds.revealed.get(&10), Some(&server_seed));
+        assert_eq!(commitment(&server_seed), roll.commitment);
+        assert_eq!(
+            rng.gen::<u64>(),
+            fair_rng(&server_seed, &roll.client_seed, roll.nonce).gen::<u64>()
+        );
+        assert_eq!(seeds.next_on(11, user).0.nonce, 1);
+    }
+
+    #[test]
+    fn set_client_seed_keeps_nonce() {
+        let mut seeds = Seeds::starting(today());
+        let user = UserId::new(1);
+        seeds.set_client_seed(user, "lucky".to_string());
+        let first = seeds.next(user).0;
+        seeds.set_client_seed(user, "other".to_string());
+        seeds.next(user);
+        seeds.set_client_seed(user, "lucky".to_string());
+
+        let again = seeds.next(user).0;
+        assert_eq!(again.client_seed, first.client_seed);
+        assert_eq!(again.nonce, 2);
+    }
+
+    #[test]
+    fn next_saves_once_per_batch() {
+        let path = store::temp_path("seeds", "json");
+        let user = UserId::new(1);
+        let saved = || fs::read_to_string(&path).unwrap();
+
+        let mut seeds = Seeds::open(path.clone()).unwrap();
+        seeds.next(user);
+        let first = saved();
+        for _ in 1..=NONCE_BATCH {
+            seeds.next(user);
+        }
+        assert_eq!(saved(), first);
+        seeds.next(user);
+        assert_ne!(saved(), first);
+
+        let mut seeds = Seeds::open(path.clone()).unwrap();
+        assert_eq!(seeds.next(user).0.nonce, 2 * NONCE_BATCH + 2);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn open_reloads_seeds() {
+        let path = store::temp_path("seeds", "json");
+        let user = UserId::new(1);
+        let (commitment, nonce) = {
+            let mut seeds = Seeds::open(path.clone()).unwrap();
+            let roll = seeds.next(user).0;
+            (roll.commitment, roll.nonce)
+        };
+
+        let mut seeds = Seeds::open(path.clone()).unwrap();
+        let roll = seeds.next(user).0;
+        assert_eq!(roll.commitment, commitment);
+        // The nonces reserved before the restart are skipped
+        assert_eq!(roll.nonce, nonce + 1 + NONCE_BATCH);
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
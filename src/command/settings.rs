@@ -0,0 +1,128 @@
+use std::{collections::HashMap, fs, io, path::PathBuf};
+
+use poise::serenity_prelude::GuildId;
+use serde::{Deserialize, Serialize};
+
+use locale::Locale;
+use render::Output;
+use table::Style;
+
+use crate::command::*;
+
+/// Dice a token can have before its table shows face counts instead of a row per die.
+pub const POOL_THRESHOLD: u16 = 12;
+
+/// Preferences of a guild, set with `/settings`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GuildSettings {
+    pub style: Style,
+    pub output: Output,
+    pub pool_threshold: u16,
+    /// Language of the replies, the one of each member when not set.
+    pub locale: Option<Locale>,
+}
+
+impl Default for GuildSettings {
+    fn default() -> Self {
+        Self {
+            style: Style::default(),
+            output: Output::default(),
+            pool_threshold: POOL_THRESHOLD,
+            locale: None,
+        }
+    }
+}
+
+/// Settings of every guild, saved as JSON so they outlive restarts.
+pub struct Settings {
+    path: Option<PathBuf>,
+    guilds: HashMap<GuildId, GuildSettings>,
+}
+
+impl Settings {
+    /// Load the settings saved in `path` and save the next changes to it.
+    pub fn open(path: PathBuf) -> io::Result<Self> {
+        let guilds = if path.exists() {
+            serde_json::from_str(&fs::read_to_string(&path)?)?
+        } else {
+            HashMap::new()
+        };
+
+        Ok(Self {
+            path: Some(path),
+            guilds,
+        })
+    }
+
+    /// Settings that are forgotten on restart.
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            guilds: HashMap::new(),
+        }
+    }
+
+    /// Settings of a guild, the defaults outside of guilds.
+    pub fn guild(&self, guild: Option<GuildId>) -> GuildSettings {
+        guild
+            .and_then(|guild| self.guilds.get(&guild))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn update(
+        &mut self,
+        guild: GuildId,
+        update: impl FnOnce(&mut GuildSettings),
+    ) -> io::Result<()> {
+        update(self.guilds.entry(guild).or_default());
+        match &self.path {
+            Some(path) => {
+                store::write_atomically(path, &serde_json::to_string_pretty(&self.guilds)?)
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn update_works() {
+        let mut settings = Settings::in_memory();
+        let guild = GuildId::new(1);
+        settings
+            .update(guild, |settings| settings.style = Style::Unicode)
+            .unwrap();
+
+        assert_eq!(settings.guild(Some(guild)).style, Style::Unicode);
+        assert_eq!(settings.guild(Some(guild)).pool_threshold, POOL_THRESHOLD);
+        assert_eq!(
+            settings.guild(Some(GuildId::new(2))),
+            GuildSettings::default()
+        );
+        assert_eq!(settings.guild(None), GuildSettings::default());
+    }
+
+    #[test]
+    fn open_reloads_settings() {
+        let path = store::temp_path("settings", "json");
+        let guild = GuildId::new(1);
+        Settings::open(path.clone())
+            .unwrap()
+            .update(guild, |settings| {
+                settings.style = Style::Minimal;
+                settings.locale = Some(Locale::French);
+            })
+            .unwrap();
+
+        let settings = Settings::open(path.clone()).unwrap();
+        assert_eq!(settings.guild(Some(guild)).style, Style::Minimal);
+        assert_eq!(settings.guild(Some(guild)).locale, Some(Locale::French));
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
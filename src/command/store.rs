@@ -0,0 +1,41 @@
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// Replace the content of `path` through a temporary file renamed over it, so a crash while
+/// writing leaves either the old content or the new one, never a file cut short.
+pub fn write_atomically(path: &Path, contents: &str) -> io::Result<()> {
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+    let temporary = PathBuf::from(temporary);
+
+    let mut file = File::create(&temporary)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    fs::rename(temporary, path)
+}
+
+/// A path in the temporary directory that no other test uses.
+#[cfg(test)]
+pub fn temp_path(prefix: &str, extension: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("{prefix}-{}.{extension}", rand::random::<u64>()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn write_atomically_replaces_content() {
+        let path = temp_path("store", "json");
+        write_atomically(&path, "first").unwrap();
+        write_atomically(&path, "second").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+        assert!(!path.with_extension("json.tmp").exists());
+
+        fs::remove_file(path).unwrap();
+    }
+}
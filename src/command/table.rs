@@ -1,11 +1,76 @@
-use std::{fmt::Display, iter};
+use std::{borrow::Cow, fmt::Display, iter, ops::Range};
 
+use itertools::Itertools;
+use poise::ChoiceParameter;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use unicode_width::UnicodeWidthStr;
+
+use super::locale::{Locale, Localize};
+
+/// Columns covered by a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Span {
+    /// Every column left in the row.
     Full,
     Columns(u8),
 }
 
-#[derive(Clone)]
+/// Format a table is exported to, to paste it elsewhere.
+#[derive(Debug, Clone, Copy, PartialEq, poise::ChoiceParameter)]
+pub enum Format {
+    /// GitHub flavored Markdown, for wikis.
+    #[name = "Markdown"]
+    Markdown,
+    /// Comma separated values, for spreadsheets.
+    #[name = "CSV"]
+    Csv,
+    /// Every row with its cells and their alignment, for scripts.
+    #[name = "JSON"]
+    Json,
+}
+
+impl Format {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Markdown => "md",
+            Format::Csv => "csv",
+            Format::Json => "json",
+        }
+    }
+}
+
+/// How the rows and columns of a table are drawn.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize, poise::ChoiceParameter,
+)]
+pub enum Style {
+    /// Columns separated by spaces, separators drawn with their own character.
+    #[default]
+    #[name = "ASCII"]
+    Ascii,
+    /// Box drawing borders with vertical lines between columns.
+    #[name = "Unicode box"]
+    #[name_localized("fr", "Cadre Unicode")]
+    Unicode,
+    /// Columns separated by two spaces, separators left blank.
+    #[name = "Minimal"]
+    #[name_localized("fr", "Minimal")]
+    Minimal,
+}
+
+impl Localize for Style {
+    fn localize(&self, locale: Locale) -> String {
+        self.localized_name(locale.discord())
+            .unwrap_or(self.name())
+            .to_string()
+    }
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Alignment {
     Left,
     Center,
@@ -13,22 +78,104 @@ pub enum Alignment {
 }
 
 impl Alignment {
+    /// Pad `s` with spaces to `width` columns of a monospace font.
+    ///
+    /// `format!` pads by characters, which misaligns wide characters like emoji and CJK.
     fn align(&self, s: &str, width: usize) -> String {
+        let (left, right) = self.padding(width.saturating_sub(s.width()));
+        format!("{}{s}{}", " ".repeat(left), " ".repeat(right))
+    }
+
+    /// Spaces on the left and on the right.
+    fn padding(&self, padding: usize) -> (usize, usize) {
+        match self {
+            Alignment::Left => (0, padding),
+            Alignment::Center => (padding / 2, padding - padding / 2),
+            Alignment::Right => (padding, 0),
+        }
+    }
+}
+
+/// Colour of a cell in ```ansi code blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Colour {
+    Red,
+    Green,
+}
+
+impl Colour {
+    /// SGR parameter of the foreground colour.
+    fn code(self) -> u8 {
         match self {
-            Alignment::Left => format!("{s:<width$}", width = width),
-            Alignment::Center => format!("{s:^width$}", width = width),
-            Alignment::Right => format!("{s:>width$}", width = width),
+            Colour::Red => 31,
+            Colour::Green => 32,
+        }
+    }
+}
+
+#[derive(Clone, Serialize)]
+pub struct Cell {
+    text: String,
+    alignment: Alignment,
+    colour: Option<Colour>,
+    span: Span,
+}
+
+impl Cell {
+    pub fn new(text: String, alignment: Alignment) -> Self {
+        Self {
+            text,
+            alignment,
+            colour: None,
+            span: Span::Columns(1),
         }
     }
+
+    pub fn coloured(self, colour: Option<Colour>) -> Self {
+        Self { colour, ..self }
+    }
+
+    pub fn spanning(self, span: Span) -> Self {
+        Self { span, ..self }
+    }
+
+    /// Text padded to `width`, with escape codes around the text alone when `ansi` so the
+    /// padding only counts visible characters.
+    fn render(&self, width: usize, ansi: bool) -> String {
+        let (left, right) = self
+            .alignment
+            .padding(width.saturating_sub(self.text.width()));
+        let text = match self.colour {
+            Some(colour) if ansi => format!("\u{1b}[{}m{}\u{1b}[0m", colour.code(), self.text),
+            _ => self.text.clone(),
+        };
+        format!("{}{text}{}", " ".repeat(left), " ".repeat(right))
+    }
+}
+
+impl From<(String, Alignment)> for Cell {
+    fn from((text, alignment): (String, Alignment)) -> Self {
+        Self::new(text, alignment)
+    }
 }
 
 #[derive(Clone)]
 pub enum TableRow {
-    Columns(Vec<(String, Alignment)>),
+    Columns(Vec<Cell>),
     FullWidth(String, Alignment),
     Separator(char),
 }
 
+impl TableRow {
+    pub fn columns<C>(cells: impl IntoIterator<Item = C>) -> Self
+    where
+        C: Into<Cell>,
+    {
+        Self::Columns(cells.into_iter().map(Into::into).collect())
+    }
+}
+
 pub struct Table {
     rows: Vec<TableRow>,
 }
@@ -50,25 +197,115 @@ impl Table {
     pub fn append_table(&mut self, other: &Table) {
         self.append_rows(&other.rows);
     }
-}
 
-impl Display for Table {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut column_widths = vec![];
-        for row in &self.rows {
-            if let TableRow::Columns(row) = row {
-                for (c, (column, _)) in row.iter().enumerate() {
-                    if column_widths.len() <= c {
-                        column_widths.push(column.len());
+    /// Horizontal bar chart of the share of every label, the longest bar is `width` wide.
+    pub fn histogram(bars: &[(String, f64)], width: usize) -> Self {
+        let max = bars.iter().map(|(_, share)| *share).fold(0.0, f64::max);
+
+        Self::new(
+            bars.iter()
+                .map(|(label, share)| {
+                    let length = if max > 0.0 {
+                        (share / max * width as f64).round() as usize
                     } else {
-                        column_widths[c] = usize::max(column_widths[c], column.len());
-                    }
-                }
+                        0
+                    };
+                    TableRow::columns(vec![
+                        (label.clone(), Alignment::Right),
+                        (format!("{:.1}%", share * 100.0), Alignment::Right),
+                        ("#".repeat(length), Alignment::Left),
+                    ])
+                })
+                .collect(),
+        )
+    }
+}
+
+/// `text` without the colours `render_ansi` adds, as it is shown to readers.
+pub fn strip_ansi(text: &str) -> Cow<'_, str> {
+    Regex::new("\u{1b}\\[[0-9;]*m")
+        .unwrap()
+        .replace_all(text, "")
+}
+
+/// Columns covered by every cell of a row with `columns` columns in all.
+fn ranges(row: &[Cell], columns: usize) -> Vec<Range<usize>> {
+    let mut start = 0;
+    row.iter()
+        .map(|cell| {
+            let end = match cell.span {
+                Span::Full => columns.max(start + 1),
+                Span::Columns(count) => start + usize::from(count.max(1)),
+            };
+            let range = start..end;
+            start = end;
+            range
+        })
+        .collect()
+}
+
+/// Width of the columns in `range` and the gaps between them.
+fn spanned(column_widths: &[usize], range: Range<usize>, gap: usize) -> usize {
+    range.len().saturating_sub(1) * gap + column_widths[range].iter().sum::<usize>()
+}
+
+impl Table {
+    /// Number of columns, a cell spanning the rest of its row counting as one.
+    fn column_count(&self) -> usize {
+        self.rows
+            .iter()
+            .filter_map(|row| match row {
+                TableRow::Columns(row) => ranges(row, 0).last().map(|range| range.end),
+                _ => None,
+            })
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Width of every column, with `gap` between columns.
+    ///
+    /// Cells that span several columns and do not fit widen the last column they span.
+    fn column_widths(&self, gap: usize) -> Vec<usize> {
+        let columns = self.column_count();
+        let rows = self
+            .rows
+            .iter()
+            .filter_map(|row| match row {
+                TableRow::Columns(row) => Some(row.iter().zip(ranges(row, columns))),
+                _ => None,
+            })
+            .flatten()
+            .collect::<Vec<_>>();
+
+        let mut column_widths = vec![0; columns];
+        for (cell, range) in &rows {
+            if range.len() == 1 {
+                column_widths[range.start] = column_widths[range.start].max(cell.text.width());
+            }
+        }
+        for (cell, range) in rows {
+            let width = spanned(&column_widths, range.clone(), gap);
+            column_widths[range.end - 1] += cell.text.width().saturating_sub(width);
+        }
+        column_widths
+    }
+
+    /// Whether a vertical line leaves each column of a row of columns towards its left.
+    fn boundaries(row: Option<&TableRow>, columns: usize) -> Vec<bool> {
+        let mut boundaries = vec![false; columns];
+        if let Some(TableRow::Columns(row)) = row {
+            boundaries.iter_mut().for_each(|boundary| *boundary = true);
+            for range in ranges(row, columns) {
+                boundaries[range.start + 1..range.end]
+                    .iter_mut()
+                    .for_each(|boundary| *boundary = false);
             }
         }
+        boundaries
+    }
 
-        let total_width = self
-            .rows
+    fn full_width(&self) -> usize {
+        self.rows
             .iter()
             .filter_map(|row| {
                 if let TableRow::FullWidth(row, _) = row {
@@ -77,40 +314,274 @@ impl Display for Table {
                     None
                 }
             })
-            .map(|row| row.len())
+            .map(|row| row.width())
             .max()
-            .unwrap_or_default();
+            .unwrap_or_default()
+    }
+
+    /// Whether some cells have a colour, shown by `render_ansi`.
+    pub fn is_coloured(&self) -> bool {
+        self.rows.iter().any(|row| match row {
+            TableRow::Columns(row) => row.iter().any(|cell| cell.colour.is_some()),
+            _ => false,
+        })
+    }
+
+    pub fn render(&self, style: Style) -> String {
+        self.render_with(style, false)
+    }
+
+    /// Like `render`, with the colours of cells as ANSI escape codes for ```ansi code blocks.
+    pub fn render_ansi(&self, style: Style) -> String {
+        self.render_with(style, true)
+    }
+
+    fn render_with(&self, style: Style, ansi: bool) -> String {
+        match style {
+            Style::Ascii => self.render_spaced(" ", Some, ansi),
+            Style::Minimal => self.render_spaced("  ", |_| None, ansi),
+            Style::Unicode => self.render_boxed(ansi),
+        }
+    }
 
+    /// Columns joined by `gap`, separators filled with the character `separator` gives them,
+    /// if any.
+    fn render_spaced(&self, gap: &str, separator: fn(char) -> Option<char>, ansi: bool) -> String {
+        let column_widths = self.column_widths(gap.width());
         let total_width = usize::max(
-            column_widths.iter().sum::<usize>() + column_widths.len().saturating_sub(1),
-            total_width,
+            column_widths.iter().sum::<usize>()
+                + column_widths.len().saturating_sub(1) * gap.width(),
+            self.full_width(),
         );
 
+        let mut result = String::new();
         for row in &self.rows {
             match row {
                 TableRow::Columns(row) => {
-                    for (c, (column, alignment)) in row.iter().enumerate() {
-                        if c > 0 {
-                            write!(f, " ")?;
-                        }
-                        write!(f, "{}", alignment.align(column, column_widths[c]))?;
-                    }
+                    result += &row
+                        .iter()
+                        .zip(ranges(row, column_widths.len()))
+                        .map(|(cell, range)| {
+                            cell.render(spanned(&column_widths, range, gap.width()), ansi)
+                        })
+                        .join(gap);
                 }
                 TableRow::FullWidth(row, alignment) => {
-                    write!(f, "{}", alignment.align(row, total_width))?;
+                    result += &alignment.align(row, total_width);
                 }
                 TableRow::Separator(row) => {
-                    write!(
-                        f,
-                        "{}",
-                        iter::repeat(row).take(total_width).collect::<String>()
-                    )?;
+                    if let Some(fill) = separator(*row) {
+                        result.extend(iter::repeat_n(fill, total_width));
+                    }
+                }
+            }
+            result.push('\n');
+        }
+
+        result
+    }
+
+    /// Box drawing borders around every row and between columns.
+    ///
+    /// `-` separators are drawn with light lines and the others with double ones. Joints only
+    /// appear where the rows around them have a line between two columns.
+    fn render_boxed(&self, ansi: bool) -> String {
+        // Columns are separated by ` │ `
+        let mut column_widths = self.column_widths(3);
+        let inner_width = |widths: &[usize]| {
+            widths.iter().map(|width| width + 2).sum::<usize>() + widths.len().saturating_sub(1)
+        };
+        // The last column takes the room left by rows wider than all columns
+        let full_width = self.full_width() + 2;
+        let missing = full_width.saturating_sub(inner_width(&column_widths));
+        if let Some(last) = column_widths.last_mut() {
+            *last += missing;
+        }
+        let inner_width = inner_width(&column_widths).max(full_width);
+
+        let boundaries = |row: Option<&TableRow>| Self::boundaries(row, column_widths.len());
+        let line = |(left, fill, right): (char, char, char), up: Vec<bool>, down: Vec<bool>| {
+            let mut line = left.to_string();
+            if column_widths.is_empty() {
+                line.extend(iter::repeat_n(fill, inner_width));
+            }
+            for (c, width) in column_widths.iter().enumerate() {
+                if c > 0 {
+                    line.push(match (up[c], down[c], fill) {
+                        (true, true, '═') => '╪',
+                        (true, false, '═') => '╧',
+                        (false, true, '═') => '╤',
+                        (true, true, _) => '┼',
+                        (true, false, _) => '┴',
+                        (false, true, _) => '┬',
+                        (false, false, _) => fill,
+                    });
+                }
+                line.extend(iter::repeat_n(fill, width + 2));
+            }
+            line.push(right);
+            line.push('\n');
+            line
+        };
+
+        let none = vec![false; column_widths.len()];
+        let mut result = line(('┌', '─', '┐'), none.clone(), boundaries(self.rows.first()));
+        for (i, row) in self.rows.iter().enumerate() {
+            match row {
+                TableRow::Columns(row) => {
+                    result.push('│');
+                    let ranges = ranges(row, column_widths.len());
+                    for (cell, range) in row.iter().zip(&ranges) {
+                        let cell = cell.render(spanned(&column_widths, range.clone(), 3), ansi);
+                        result += &format!(" {cell} │");
+                    }
+                    let end = ranges.last().map(|range| range.end).unwrap_or_default();
+                    for width in &column_widths[end..] {
+                        result += &format!(" {} │", " ".repeat(*width));
+                    }
+                    result.push('\n');
+                }
+                TableRow::FullWidth(row, alignment) => {
+                    result += &format!("│ {} │\n", alignment.align(row, inner_width - 2));
+                }
+                TableRow::Separator(separator) => {
+                    let ends = if *separator == '-' {
+                        ('├', '─', '┤')
+                    } else {
+                        ('╞', '═', '╡')
+                    };
+                    let up = if i > 0 {
+                        boundaries(self.rows.get(i - 1))
+                    } else {
+                        none.clone()
+                    };
+                    result += &line(ends, up, boundaries(self.rows.get(i + 1)));
+                }
+            }
+        }
+        result += &line(('└', '─', '┘'), boundaries(self.rows.last()), none);
+
+        result
+    }
+}
+
+impl Table {
+    pub fn export(&self, format: Format) -> String {
+        match format {
+            Format::Markdown => self.to_markdown(),
+            Format::Csv => self.to_csv(),
+            Format::Json => self.to_json(),
+        }
+    }
+
+    /// Text of every row that is not a separator, spanning cells and full width rows filling
+    /// their first column and leaving the others empty.
+    fn grid(&self) -> Vec<Vec<&str>> {
+        let columns = self.column_count().max(1);
+        self.rows
+            .iter()
+            .filter_map(|row| match row {
+                TableRow::Columns(row) => {
+                    let mut cells = vec![""; columns];
+                    for (cell, range) in row.iter().zip(ranges(row, columns)) {
+                        cells[range.start] = &cell.text;
+                    }
+                    Some(cells)
+                }
+                TableRow::FullWidth(text, _) => {
+                    let mut cells = vec![""; columns];
+                    cells[0] = text;
+                    Some(cells)
+                }
+                TableRow::Separator(_) => None,
+            })
+            .collect()
+    }
+
+    /// GitHub flavored Markdown table, the first row as header.
+    ///
+    /// Columns are aligned like the first cell that fits in them alone.
+    fn to_markdown(&self) -> String {
+        let columns = self.column_count().max(1);
+        let mut alignments = vec![None; columns];
+        for row in &self.rows {
+            if let TableRow::Columns(row) = row {
+                for (cell, range) in row.iter().zip(ranges(row, columns)) {
+                    if range.len() == 1 {
+                        alignments[range.start].get_or_insert(&cell.alignment);
+                    }
                 }
             }
-            writeln!(f);
         }
 
-        Ok(())
+        let line = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+        let mut grid = self.grid().into_iter().map(|row| {
+            line(
+                row.into_iter()
+                    .map(|cell| cell.replace('|', "\\|"))
+                    .collect(),
+            )
+        });
+        let mut result = grid
+            .next()
+            .unwrap_or_else(|| line(vec![String::new(); columns]));
+        result += &line(
+            alignments
+                .into_iter()
+                .map(|alignment| {
+                    match alignment {
+                        Some(Alignment::Left) | None => ":--",
+                        Some(Alignment::Center) => ":-:",
+                        Some(Alignment::Right) => "--:",
+                    }
+                    .to_string()
+                })
+                .collect(),
+        );
+        result.extend(grid);
+        result
+    }
+
+    fn to_csv(&self) -> String {
+        self.grid()
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|cell| {
+                        if cell.contains([',', '"', '\n']) {
+                            format!("\"{}\"", cell.replace('"', "\"\""))
+                        } else {
+                            cell.to_string()
+                        }
+                    })
+                    .join(",")
+                    + "\r\n"
+            })
+            .collect()
+    }
+
+    /// Every row with its type, separators included.
+    fn to_json(&self) -> String {
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| match row {
+                TableRow::Columns(cells) => json!({ "type": "columns", "cells": cells }),
+                TableRow::FullWidth(text, alignment) => {
+                    json!({ "type": "full_width", "text": text, "alignment": alignment })
+                }
+                TableRow::Separator(character) => {
+                    json!({ "type": "separator", "character": character })
+                }
+            })
+            .collect::<Vec<_>>();
+        serde_json::to_string_pretty(&rows).expect("Rows are valid JSON")
+    }
+}
+
+impl Display for Table {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render(Style::Ascii))
     }
 }
 
@@ -125,6 +596,39 @@ mod test {
         s.lines().map(|s| s.trim_end()).join("\n")
     }
 
+    fn people() -> Table {
+        Table::new(vec![
+            TableRow::FullWidth("PEOPLE".to_string(), Alignment::Center),
+            TableRow::Separator('#'),
+            TableRow::columns(vec![
+                ("Name".to_string(), Alignment::Left),
+                ("Age".to_string(), Alignment::Right),
+                ("City".to_string(), Alignment::Left),
+            ]),
+            TableRow::Separator('-'),
+            TableRow::columns(vec![
+                ("Alice".to_string(), Alignment::Left),
+                ("30".to_string(), Alignment::Right),
+                ("New York".to_string(), Alignment::Left),
+            ]),
+            TableRow::columns(vec![
+                ("Bob".to_string(), Alignment::Left),
+                ("20".to_string(), Alignment::Right),
+                ("Los Angeles".to_string(), Alignment::Left),
+            ]),
+            TableRow::columns(vec![
+                ("Charlie".to_string(), Alignment::Left),
+                ("5".to_string(), Alignment::Right),
+                ("Chicago".to_string(), Alignment::Left),
+            ]),
+            TableRow::Separator('='),
+            TableRow::columns(vec![
+                ("TOTAL".to_string(), Alignment::Left),
+                ("55".to_string(), Alignment::Right),
+            ]),
+        ])
+    }
+
     #[test]
     fn table_works() {
         assert_eq!(
@@ -141,37 +645,262 @@ mod test {
                  TOTAL    55"}
                 .to_string()
             ),
+            trim(people().to_string())
+        );
+    }
+
+    #[test]
+    fn unicode_style_works() {
+        assert_eq!(
+            indoc! {r"
+                ┌─────────────────────────────┐
+                │           PEOPLE            │
+                ╞═════════╤═════╤═════════════╡
+                │ Name    │ Age │ City        │
+                ├─────────┼─────┼─────────────┤
+                │ Alice   │  30 │ New York    │
+                │ Bob     │  20 │ Los Angeles │
+                │ Charlie │   5 │ Chicago     │
+                ╞═════════╪═════╪═════════════╡
+                │ TOTAL   │  55 │             │
+                └─────────┴─────┴─────────────┘
+            "},
+            people().render(Style::Unicode)
+        );
+    }
+
+    #[test]
+    fn unicode_style_widens_last_column() {
+        assert_eq!(
+            indoc! {r"
+                ┌────────────────┐
+                │ A longer title │
+                ├───┬────────────┤
+                │ 1 │ (d6)       │
+                └───┴────────────┘
+            "},
+            Table::new(vec![
+                TableRow::FullWidth("A longer title".to_string(), Alignment::Left),
+                TableRow::Separator('-'),
+                TableRow::columns(vec![
+                    ("1".to_string(), Alignment::Right),
+                    ("(d6)".to_string(), Alignment::Left),
+                ]),
+            ])
+            .render(Style::Unicode)
+        );
+    }
+
+    #[test]
+    fn minimal_style_works() {
+        assert_eq!(
+            trim(
+                indoc! {r"
+                          PEOPLE
+
+                 Name     Age  City
+
+                 Alice     30  New York
+                 Bob       20  Los Angeles
+                 Charlie    5  Chicago
+
+                 TOTAL     55"}
+                .to_string()
+            ),
+            trim(people().render(Style::Minimal))
+        );
+    }
+
+    #[test]
+    fn wide_characters_work() {
+        let table = Table::new(vec![
+            TableRow::FullWidth("🎲 ROLL".to_string(), Alignment::Center),
+            TableRow::Separator('-'),
+            TableRow::columns(vec![
+                ("日本".to_string(), Alignment::Left),
+                ("1".to_string(), Alignment::Right),
+            ]),
+            TableRow::columns(vec![
+                ("é".to_string(), Alignment::Left),
+                ("20".to_string(), Alignment::Right),
+            ]),
+        ]);
+        assert_eq!(
+            table.render(Style::Unicode),
+            indoc! {"
+                ┌───────────┐
+                │  🎲 ROLL  │
+                ├──────┬────┤
+                │ 日本 │  1 │
+                │ é    │ 20 │
+                └──────┴────┘
+            "}
+        );
+    }
+
+    #[test]
+    fn ansi_works() {
+        let table = Table::new(vec![
+            TableRow::columns(vec![
+                Cell::new("20".to_string(), Alignment::Right).coloured(Some(Colour::Green)),
+                Cell::new("(d20)".to_string(), Alignment::Left),
+            ]),
+            TableRow::columns(vec![
+                Cell::new("1".to_string(), Alignment::Right).coloured(Some(Colour::Red)),
+                Cell::new("(d6)".to_string(), Alignment::Left),
+            ]),
+        ]);
+        assert!(table.is_coloured());
+        assert!(!people().is_coloured());
+        assert_eq!(
+            table.render_ansi(Style::Ascii),
+            "\u{1b}[32m20\u{1b}[0m (d20)\n \u{1b}[31m1\u{1b}[0m (d6) \n"
+        );
+        assert_eq!(table.render(Style::Ascii), "20 (d20)\n 1 (d6) \n");
+        assert_eq!(
+            strip_ansi(&table.render_ansi(Style::Ascii)),
+            table.render(Style::Ascii)
+        );
+    }
+
+    fn spans() -> Table {
+        Table::new(vec![
+            TableRow::columns(vec![
+                Cell::new("Advantage (take 2)".to_string(), Alignment::Center)
+                    .spanning(Span::Columns(2)),
+                Cell::new("Sum".to_string(), Alignment::Left),
+            ]),
+            TableRow::Separator('-'),
+            TableRow::columns(vec![
+                ("12".to_string(), Alignment::Right),
+                ("(d20)".to_string(), Alignment::Left),
+                ("19".to_string(), Alignment::Right),
+            ]),
+            TableRow::columns(vec![
+                ("7".to_string(), Alignment::Right),
+                ("(d20)".to_string(), Alignment::Left),
+            ]),
+            TableRow::Separator('='),
+            TableRow::columns(vec![
+                Cell::new("Total".to_string(), Alignment::Left).spanning(Span::Full)
+            ]),
+        ])
+    }
+
+    #[test]
+    fn spans_work() {
+        assert_eq!(
+            trim(spans().render(Style::Ascii)),
+            indoc! {"
+                Advantage (take 2) Sum
+                ----------------------
+                12 (d20)            19
+                 7 (d20)
+                ======================
+                Total"}
+        );
+    }
+
+    #[test]
+    fn unicode_spans_work() {
+        assert_eq!(
+            spans().render(Style::Unicode),
+            indoc! {"
+                ┌────────────────────┬─────┐
+                │ Advantage (take 2) │ Sum │
+                ├────┬───────────────┼─────┤
+                │ 12 │ (d20)         │  19 │
+                │  7 │ (d20)         │     │
+                ╞════╧═══════════════╧═════╡
+                │ Total                    │
+                └──────────────────────────┘
+            "}
+        );
+    }
+
+    #[test]
+    fn markdown_works() {
+        assert_eq!(
+            people().export(Format::Markdown),
+            indoc! {"
+                | PEOPLE |  |  |
+                | :-- | --: | :-- |
+                | Name | Age | City |
+                | Alice | 30 | New York |
+                | Bob | 20 | Los Angeles |
+                | Charlie | 5 | Chicago |
+                | TOTAL | 55 |  |
+            "}
+        );
+    }
+
+    #[test]
+    fn csv_works() {
+        let table = Table::new(vec![
+            TableRow::columns(vec![
+                Cell::new("Rolls, \"best\"".to_string(), Alignment::Left)
+                    .spanning(Span::Columns(2)),
+                Cell::new("Sum".to_string(), Alignment::Left),
+            ]),
+            TableRow::Separator('-'),
+            TableRow::columns(vec![
+                ("12".to_string(), Alignment::Right),
+                ("(d20)".to_string(), Alignment::Left),
+                ("19".to_string(), Alignment::Right),
+            ]),
+        ]);
+        assert_eq!(
+            table.export(Format::Csv),
+            "\"Rolls, \"\"best\"\"\",,Sum\r\n12,(d20),19\r\n"
+        );
+    }
+
+    #[test]
+    fn json_works() {
+        let json =
+            serde_json::from_str::<serde_json::Value>(&spans().export(Format::Json)).unwrap();
+        assert_eq!(
+            json[0],
+            json!({
+                "type": "columns",
+                "cells": [
+                    {
+                        "text": "Advantage (take 2)",
+                        "alignment": "center",
+                        "colour": null,
+                        "span": { "columns": 2 }
+                    },
+                    { "text": "Sum", "alignment": "left", "colour": null, "span": { "columns": 1 } }
+                ]
+            })
+        );
+        assert_eq!(json[1], json!({ "type": "separator", "character": "-" }));
+        assert_eq!(json[5]["cells"][0]["span"], json!("full"));
+    }
+
+    #[test]
+    fn histogram_works() {
+        assert_eq!(
+            trim(
+                indoc! {r"
+                    1 12.5% #####
+                    2 25.0% ##########
+                    3 50.0% ####################
+                 4..6 12.5% #####
+                    7  0.0%"}
+                .to_string()
+            ),
             trim(
-                Table::new(vec![
-                    TableRow::FullWidth("PEOPLE".to_string(), Alignment::Center),
-                    TableRow::Separator('#'),
-                    TableRow::Columns(vec![
-                        ("Name".to_string(), Alignment::Left),
-                        ("Age".to_string(), Alignment::Right),
-                        ("City".to_string(), Alignment::Left),
-                    ]),
-                    TableRow::Separator('-'),
-                    TableRow::Columns(vec![
-                        ("Alice".to_string(), Alignment::Left),
-                        ("30".to_string(), Alignment::Right),
-                        ("New York".to_string(), Alignment::Left),
-                    ]),
-                    TableRow::Columns(vec![
-                        ("Bob".to_string(), Alignment::Left),
-                        ("20".to_string(), Alignment::Right),
-                        ("Los Angeles".to_string(), Alignment::Left),
-                    ]),
-                    TableRow::Columns(vec![
-                        ("Charlie".to_string(), Alignment::Left),
-                        ("5".to_string(), Alignment::Right),
-                        ("Chicago".to_string(), Alignment::Left),
-                    ]),
-                    TableRow::Separator('='),
-                    TableRow::Columns(vec![
-                        ("TOTAL".to_string(), Alignment::Left),
-                        ("55".to_string(), Alignment::Right),
-                    ]),
-                ])
+                Table::histogram(
+                    &[
+                        ("1".to_string(), 0.125),
+                        ("2".to_string(), 0.25),
+                        ("3".to_string(), 0.5),
+                        ("4..6".to_string(), 0.125),
+                        ("7".to_string(), 0.0),
+                    ],
+                    20
+                )
                 .to_string()
             )
         );
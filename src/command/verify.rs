@@ -0,0 +1,49 @@
+use lang::{commitment, fair_rng, Tokens};
+use locale::Locale;
+
+use crate::command::*;
+
+/// Recompute a roll from a revealed server seed.
+#[poise::command(
+    slash_command,
+    prefix_command,
+    name_localized("fr", "verifier"),
+    description_localized("fr", "Recalculer un lancer à partir d'une graine révélée.")
+)]
+pub async fn verify(
+    ctx: Context<'_>,
+    #[description = "Revealed server seed of the day of the roll"]
+    #[name_localized("fr", "graine_serveur")]
+    #[description_localized("fr", "Graine serveur révélée du jour du lancer")]
+    server_seed: String,
+    #[description = "Client seed of the roll"]
+    #[name_localized("fr", "graine_client")]
+    #[description_localized("fr", "Graine client du lancer")]
+    client_seed: String,
+    #[description = "Nonce of the roll"] nonce: u64,
+    #[description = "What was rolled, space separated"]
+    #[name_localized("fr", "elements")]
+    #[description_localized("fr", "Ce qui a été lancé, séparé par des espaces")]
+    tokens: Tokens,
+) -> Result {
+    let limits = &ctx.data().limits;
+    tokens.check_limits(limits)?;
+
+    let locale = Locale::of(ctx);
+    let settings = ctx.data().guild_settings(ctx.guild_id());
+    let table = tokens
+        .roll(&mut fair_rng(&server_seed, &client_seed, nonce))
+        .table(locale, usize::from(settings.pool_threshold))
+        .render(settings.style);
+    let reply = format!(
+        "\
+```rust
+{table}
+```
+{}",
+        locale
+            .catalog()
+            .verify_commitment(&commitment(&server_seed))
+    );
+    say(ctx, reply).await
+}
@@ -2,6 +2,8 @@
 #[deny(unused_imports)]
 mod command;
 
+use std::sync::Mutex;
+
 use anyhow::Context as _;
 use poise::serenity_prelude as serenity;
 use serenity::prelude::*;
@@ -18,18 +20,69 @@ async fn serenity(
         .get("DISCORD_TOKEN")
         .context("'DISCORD_TOKEN' was not found")?;
 
+    // Optional caps on how much a single roll can do, see `Limits`
+    let mut limits = Limits::default();
+    if let Some(dice) = secrets.get("MAX_DICE") {
+        limits.dice = dice.parse().context("'MAX_DICE' is not a number")?;
+    }
+    if let Some(tokens) = secrets.get("MAX_TOKENS") {
+        limits.tokens = tokens.parse().context("'MAX_TOKENS' is not a number")?;
+    }
+    if let Some(output_length) = secrets.get("MAX_OUTPUT_LENGTH") {
+        limits.output_length = output_length
+            .parse()
+            .context("'MAX_OUTPUT_LENGTH' is not a number")?;
+    }
+
+    // Every roll is appended to this file so `/replay` can show it again
+    let log_path = secrets
+        .get("ROLL_LOG")
+        .unwrap_or_else(|| "rolls.jsonl".to_string());
+    let log = RollLog::open(log_path.into()).context("Cannot open the roll log")?;
+
+    // Server seeds and nonces, so rolls stay verifiable across restarts
+    let seeds_path = secrets
+        .get("SEEDS")
+        .unwrap_or_else(|| "seeds.json".to_string());
+    let seeds = Seeds::open(seeds_path.into()).context("Cannot open the seeds")?;
+
+    // Preferences of every guild, changed with `/settings`
+    let settings_path = secrets
+        .get("SETTINGS")
+        .unwrap_or_else(|| "settings.json".to_string());
+    let settings = Settings::open(settings_path.into()).context("Cannot open the settings")?;
+
     // Set gateway intents, which decides what events the bot will be notified about
     let intents = GatewayIntents::GUILD_MESSAGES | GatewayIntents::MESSAGE_CONTENT;
 
     let framework = poise::Framework::builder()
         .options(poise::FrameworkOptions {
-            commands: vec![roll(), help()],
+            commands: vec![
+                roll(),
+                chance(),
+                compare(),
+                fairness(),
+                odds(),
+                replay(),
+                export(),
+                seed(),
+                configure(),
+                verify(),
+                help(),
+            ],
+            on_error: |error| Box::pin(on_error(error)),
             ..Default::default()
         })
         .setup(|ctx, _ready, framework| {
             Box::pin(async move {
                 poise::builtins::register_globally(ctx, &framework.options().commands).await?;
-                Ok(Data {})
+                Ok(Data {
+                    limits,
+                    seeds: Mutex::new(seeds),
+                    faces: Mutex::new(Faces::from_log(&log)),
+                    log: Mutex::new(log),
+                    settings: Mutex::new(settings),
+                })
             })
         })
         .build();